@@ -0,0 +1,35 @@
+//! Compares parsing a response with `serde_json` and with `simd-json`, which
+//! is what the `simd` feature switches to. Run it with:
+//!
+//! ```sh
+//! cargo bench --features simd
+//! ```
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use rspotify::model::{Page, SavedTrack};
+
+/// A page of 50 saved tracks, the largest one the endpoint returns.
+const SAVED_TRACKS: &str = include_str!("fixtures/saved_tracks.json");
+
+fn parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("saved_tracks");
+    group.throughput(Throughput::Bytes(SAVED_TRACKS.len() as u64));
+
+    group.bench_function("serde_json", |b| {
+        b.iter(|| serde_json::from_str::<Page<SavedTrack>>(black_box(SAVED_TRACKS)).unwrap())
+    });
+
+    // The response is copied on every iteration like the client does, since
+    // `simd-json` parses it in place.
+    group.bench_function("simd_json", |b| {
+        b.iter(|| {
+            let mut input = black_box(SAVED_TRACKS).as_bytes().to_vec();
+            simd_json::serde::from_slice::<Page<SavedTrack>>(&mut input).unwrap()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, parse);
+criterion_main!(benches);
@@ -20,7 +20,6 @@ use std::{
     collections::HashMap,
     env, fs,
     path::PathBuf,
-    sync::{Arc, Mutex},
 };
 
 #[derive(Debug, Responder)]
@@ -168,8 +167,9 @@ fn playlist(cookies: Cookies) -> AppResponse {
         return AppResponse::Redirect(Redirect::to("/"));
     }
 
-    let token = spotify.read_token_cache(false).unwrap();
-    spotify.token = Arc::new(Mutex::new(token));
+    if let Some(token) = spotify.read_token_cache(false).unwrap() {
+        spotify.set_token(token);
+    }
     let playlists = spotify
         .current_user_playlists()
         .take(50)
@@ -190,7 +190,9 @@ fn me(cookies: Cookies) -> AppResponse {
         return AppResponse::Redirect(Redirect::to("/"));
     }
 
-    spotify.token = Arc::new(Mutex::new(spotify.read_token_cache(false).unwrap()));
+    if let Some(token) = spotify.read_token_cache(false).unwrap() {
+        spotify.set_token(token);
+    }
     match spotify.me() {
         Ok(user_info) => AppResponse::Json(json!(user_info)),
         Err(_) => AppResponse::Redirect(Redirect::to("/")),
@@ -2,7 +2,7 @@
 //! authentication method without the need for user's interaction for oauth
 //! requests. You still need to authenticate the usual way at least once to
 //! obtain the refresh token, and you may need to obtain a new one if you change
-//! the required scopes.
+//! the required scopes, which can be checked with `OAuth::needs_reauth`.
 //!
 //! The cache generated by `get_token` uses the refresh token under the hood to
 //! automatically authenticate the user. This example shows how it's done
@@ -71,14 +71,14 @@ async fn main() {
         .expect("couldn't authenticate successfully");
     // Token refreshing works as well, but should with the one generated in the
     // previous request
-    let prev_token = spotify.get_token().lock().await.unwrap().clone();
+    let prev_token = spotify.token().await.unwrap();
     do_things(spotify).await;
 
     // At a different time, the refresh token can be used to refresh an access
     // token directly and run requests:
     println!(">>> Session two, running some requests:");
     let spotify = AuthCodeSpotify::new(creds.clone(), oauth.clone());
-    *spotify.token.lock().await.unwrap() = prev_token.clone();
+    spotify.set_token(prev_token.clone()).await;
     // No `prompt_for_user_token` needed.
     spotify
         .refresh_token()
@@ -90,7 +90,7 @@ async fn main() {
     // refresh token that was obtained at the beginning.
     println!(">>> Session three, running some requests:");
     let spotify = AuthCodeSpotify::new(creds, oauth);
-    *spotify.token.lock().await.unwrap() = prev_token;
+    spotify.set_token(prev_token).await;
     spotify
         .refresh_token()
         .await
@@ -8,6 +8,117 @@ pub type Headers = HashMap<String, String>;
 pub type Query<'a> = HashMap<&'a str, &'a str>;
 pub type Form<'a> = HashMap<&'a str, &'a str>;
 
+/// The HTTP methods supported by the Spotify API.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Method {
+    Get,
+    Post,
+    Put,
+    Delete,
+}
+
+/// The parameters whose values are secret, which must never be logged.
+const SENSITIVE_PARAMS: [&str; 5] = [
+    "access_token",
+    "refresh_token",
+    "client_secret",
+    "code",
+    "code_verifier",
+];
+const REDACTED: &str = "<redacted>";
+
+/// Redacts the values of the sensitive parameters in a list of `key=value`
+/// pairs separated by `&`, such as an URL's query or a form body.
+fn redact_pairs(pairs: &str) -> String {
+    pairs
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((key, _)) if SENSITIVE_PARAMS.contains(&key) => format!("{key}={REDACTED}"),
+            _ => pair.to_owned(),
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Redacts the values of the sensitive fields in a JSON value, recursively.
+fn redact_json(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if SENSITIVE_PARAMS.contains(&key.as_str()) {
+                    *value = Value::from(REDACTED);
+                } else {
+                    redact_json(value);
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(redact_json),
+        _ => {}
+    }
+}
+
+/// Redacts an URL so that it can be logged. Tokens may appear in its query.
+pub(crate) fn redact_url(url: &str) -> String {
+    match url.split_once('?') {
+        Some((base, query)) => format!("{base}?{}", redact_pairs(query)),
+        None => url.to_owned(),
+    }
+}
+
+/// Redacts the body of a request or a response so that it can be logged,
+/// which is either JSON or a form.
+pub(crate) fn redact_body(body: &str) -> String {
+    match serde_json::from_str::<Value>(body) {
+        Ok(mut value) => {
+            redact_json(&mut value);
+            value.to_string()
+        }
+        Err(_) => redact_pairs(body),
+    }
+}
+
+/// Logs the requests made by an HTTP client and their responses, unless it's
+/// disabled. The headers are never logged, since they include the
+/// authorization token.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct RequestLogger {
+    pub(crate) enabled: bool,
+}
+
+impl Default for RequestLogger {
+    fn default() -> Self {
+        RequestLogger { enabled: true }
+    }
+}
+
+impl RequestLogger {
+    /// Logs a request that is about to be made. The method and the URL are
+    /// logged with the `debug` level, and the body with `trace`.
+    pub(crate) fn request(&self, method: &str, url: &str, body: Option<&str>) {
+        if !self.enabled {
+            return;
+        }
+
+        log::debug!("Making request: {} {}", method, redact_url(url));
+        if let Some(body) = body {
+            log::trace!("Request body: {}", redact_body(body));
+        }
+    }
+
+    /// Logs the status code of a response with the `debug` level, and its
+    /// body with `trace`.
+    pub(crate) fn response(&self, method: &str, url: &str, status: u16, body: Option<&str>) {
+        if !self.enabled {
+            return;
+        }
+
+        log::debug!("Response: {} {} {}", status, method, redact_url(url));
+        if let Some(body) = body {
+            log::trace!("Response body: {}", redact_body(body));
+        }
+    }
+}
+
 /// This trait represents the interface to be implemented for an HTTP client,
 /// which is kept separate from the Spotify client for cleaner code. Thus, it
 /// also requires other basic traits that are needed for the Spotify client.
@@ -30,6 +141,12 @@ pub trait BaseHttpClient: Send + Default + Clone + fmt::Debug {
         payload: &Query,
     ) -> Result<String, Self::Error>;
 
+    /// Performs a `GET` request and returns the raw body of the response, for
+    /// binary contents such as audio. Unlike in the rest of the requests, the
+    /// body of the response is never logged.
+    async fn get_bytes(&self, url: &str, headers: Option<&Headers>)
+        -> Result<Vec<u8>, Self::Error>;
+
     async fn post(
         &self,
         url: &str,
@@ -58,3 +175,32 @@ pub trait BaseHttpClient: Send + Default + Clone + fmt::Debug {
         payload: &Value,
     ) -> Result<String, Self::Error>;
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_redact_url() {
+        assert_eq!(
+            redact_url("https://api.spotify.com/v1/me?market=ES&access_token=secret"),
+            "https://api.spotify.com/v1/me?market=ES&access_token=<redacted>"
+        );
+        assert_eq!(
+            redact_url("https://api.spotify.com/v1/me"),
+            "https://api.spotify.com/v1/me"
+        );
+    }
+
+    #[test]
+    fn test_redact_body() {
+        assert_eq!(
+            redact_body("grant_type=refresh_token&refresh_token=secret"),
+            "grant_type=refresh_token&refresh_token=<redacted>"
+        );
+        assert_eq!(
+            redact_body(r#"{"access_token":"secret","nested":[{"code":"secret"}],"scope":"a b"}"#),
+            r#"{"access_token":"<redacted>","nested":[{"code":"<redacted>"}],"scope":"a b"}"#
+        );
+    }
+}
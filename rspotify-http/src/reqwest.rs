@@ -1,12 +1,12 @@
 //! The client implementation for the reqwest HTTP client, which is async by
 //! default.
 
-use super::{BaseHttpClient, Form, Headers, Query};
+use super::{common::RequestLogger, BaseHttpClient, Form, Headers, Query};
 
-use std::convert::TryInto;
+use std::convert::TryFrom;
 
 use maybe_async::async_impl;
-use reqwest::{Method, RequestBuilder};
+use reqwest::{header::HeaderMap, Method, RequestBuilder};
 use serde_json::Value;
 
 /// Custom enum that contains all the possible errors that may occur when using
@@ -24,6 +24,7 @@ use serde_json::Value;
 /// match response {
 ///     Ok(data) => println!("request succeeded: {:?}", data),
 ///     Err(HttpError::Client(e)) => eprintln!("request failed: {}", e),
+///     Err(HttpError::InvalidHeader(e)) => eprintln!("invalid header: {}", e),
 ///     Err(HttpError::StatusCode(response)) => {
 ///         let code = response.status().as_u16();
 ///         match response.json::<rspotify_model::ApiError>().await {
@@ -47,15 +48,51 @@ pub enum ReqwestError {
     /// serialized into `rspotify_model::ApiError`.
     #[error("status code {}", reqwest::Response::status(.0))]
     StatusCode(reqwest::Response),
+
+    /// One of the headers couldn't be converted into a valid HTTP header,
+    /// e.g., because its value contains characters that aren't visible ASCII.
+    #[error("invalid header: {0}")]
+    InvalidHeader(String),
+}
+
+impl ReqwestError {
+    /// Returns the status code of the response in case the request was made
+    /// but the server returned an unsuccessful status code.
+    #[must_use]
+    pub fn status_code(&self) -> Option<u16> {
+        match self {
+            Self::StatusCode(response) => Some(response.status().as_u16()),
+            _ => None,
+        }
+    }
+
+    /// Consumes the error and returns the body of the response in case the
+    /// request was made but the server returned an unsuccessful status code.
+    /// It usually includes an error message from Spotify.
+    pub async fn into_response_body(self) -> Option<String> {
+        match self {
+            Self::StatusCode(response) => response.text().await.ok(),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Default, Debug, Clone)]
 pub struct ReqwestClient {
     /// reqwest needs an instance of its client to perform requests.
     client: reqwest::Client,
+    logger: RequestLogger,
 }
 
 impl ReqwestClient {
+    /// Enables or disables logging the requests and their responses, which
+    /// is enabled by default. Their secrets are redacted either way.
+    #[must_use]
+    pub fn with_logging(mut self, enabled: bool) -> Self {
+        self.logger.enabled = enabled;
+        self
+    }
+
     async fn request<D>(
         &self,
         method: Method,
@@ -68,30 +105,39 @@ impl ReqwestClient {
     {
         let mut request = self.client.request(method.clone(), url);
 
-        // Setting the headers, if any
+        // Setting the headers, if any. They need to be converted into a
+        // `reqwest::HeaderMap`, which fails if any of them isn't valid, e.g.,
+        // if the token or a configured value isn't ASCII.
+        //
+        // The content-type header will be set automatically.
         if let Some(headers) = headers {
-            // The headers need to be converted into a `reqwest::HeaderMap`,
-            // which won't fail as long as its contents are ASCII. This is an
-            // internal function, so the condition cannot be broken by the user
-            // and will always be true.
-            //
-            // The content-type header will be set automatically.
-            let headers = headers.try_into().unwrap();
-
+            let headers = HeaderMap::try_from(headers)
+                .map_err(|err| ReqwestError::InvalidHeader(err.to_string()))?;
             request = request.headers(headers);
         }
 
         // Configuring the request for the specific type (get/post/put/delete)
-        request = add_data(request);
+        let request = add_data(request).build()?;
+        let url = request.url().to_string();
+        let body = request
+            .body()
+            .and_then(reqwest::Body::as_bytes)
+            .map(String::from_utf8_lossy);
+        self.logger.request(method.as_str(), &url, body.as_deref());
 
         // Finally performing the request and handling the response
-        log::info!("Making request {:?}", request);
-        let response = request.send().await?;
+        let response = self.client.execute(request).await?;
+        let status = response.status();
 
         // Making sure that the status code is OK
-        if response.status().is_success() {
-            response.text().await.map_err(Into::into)
+        if status.is_success() {
+            let text = response.text().await?;
+            self.logger
+                .response(method.as_str(), &url, status.as_u16(), Some(&text));
+            Ok(text)
         } else {
+            self.logger
+                .response(method.as_str(), &url, status.as_u16(), None);
             Err(ReqwestError::StatusCode(response))
         }
     }
@@ -112,6 +158,31 @@ impl BaseHttpClient for ReqwestClient {
             .await
     }
 
+    async fn get_bytes(
+        &self,
+        url: &str,
+        headers: Option<&Headers>,
+    ) -> Result<Vec<u8>, Self::Error> {
+        let mut request = self.client.get(url);
+        if let Some(headers) = headers {
+            let headers = HeaderMap::try_from(headers)
+                .map_err(|err| ReqwestError::InvalidHeader(err.to_string()))?;
+            request = request.headers(headers);
+        }
+
+        self.logger.request(Method::GET.as_str(), url, None);
+        let response = request.send().await?;
+        let status = response.status();
+        self.logger
+            .response(Method::GET.as_str(), url, status.as_u16(), None);
+
+        if status.is_success() {
+            Ok(response.bytes().await?.to_vec())
+        } else {
+            Err(ReqwestError::StatusCode(response))
+        }
+    }
+
     #[inline]
     async fn post(
         &self,
@@ -1,8 +1,8 @@
 //! The client implementation for the ureq HTTP client, which is blocking.
 
-use super::{BaseHttpClient, Form, Headers, Query};
+use super::{common::RequestLogger, BaseHttpClient, Form, Headers, Query};
 
-use std::io;
+use std::io::{self, Read};
 
 use maybe_async::sync_impl;
 use serde_json::Value;
@@ -50,10 +50,42 @@ pub enum UreqError {
     StatusCode(ureq::Response),
 }
 
+impl UreqError {
+    /// Returns the status code of the response in case the request was made
+    /// but the server returned an unsuccessful status code.
+    #[must_use]
+    pub fn status_code(&self) -> Option<u16> {
+        match self {
+            Self::StatusCode(response) => Some(response.status()),
+            _ => None,
+        }
+    }
+
+    /// Consumes the error and returns the body of the response in case the
+    /// request was made but the server returned an unsuccessful status code.
+    /// It usually includes an error message from Spotify.
+    pub fn into_response_body(self) -> Option<String> {
+        match self {
+            Self::StatusCode(response) => response.into_string().ok(),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Default, Debug, Clone)]
-pub struct UreqClient {}
+pub struct UreqClient {
+    logger: RequestLogger,
+}
 
 impl UreqClient {
+    /// Enables or disables logging the requests and their responses, which
+    /// is enabled by default. Their secrets are redacted either way.
+    #[must_use]
+    pub fn with_logging(mut self, enabled: bool) -> Self {
+        self.logger.enabled = enabled;
+        self
+    }
+
     /// The request handling in ureq is split in three parts:
     ///
     /// * The initial request (POST, GET, ...) is given as the `request`
@@ -62,10 +94,14 @@ impl UreqClient {
     ///   for all requests.
     /// * The request is finished and performed with the `send_request` function
     ///   (JSON, a form...).
+    ///
+    /// The body is only used for logging, and it's only given when the `trace`
+    /// level is enabled to avoid serializing it otherwise.
     fn request<D>(
         &self,
         mut request: Request,
         headers: Option<&Headers>,
+        body: Option<String>,
         send_request: D,
     ) -> Result<String, UreqError>
     where
@@ -78,18 +114,40 @@ impl UreqClient {
             }
         }
 
-        log::info!("Making request {:?}", request);
+        let method = request.method().to_owned();
+        let url = request.url().to_owned();
+        self.logger.request(&method, &url, body.as_deref());
+
         // Converting errors from ureq into our custom error types
         match send_request(request) {
-            Ok(response) => response.into_string().map_err(Into::into),
+            Ok(response) => {
+                let status = response.status();
+                let text = response.into_string()?;
+                self.logger.response(&method, &url, status, Some(&text));
+                Ok(text)
+            }
             Err(err) => match err {
-                ureq::Error::Status(_, response) => Err(UreqError::StatusCode(response)),
+                ureq::Error::Status(status, response) => {
+                    self.logger.response(&method, &url, status, None);
+                    Err(UreqError::StatusCode(response))
+                }
                 ureq::Error::Transport(transport) => Err(UreqError::Transport(transport)),
             },
         }
     }
 }
 
+/// Whether the request bodies are going to be logged, so that they're only
+/// serialized when needed.
+fn logs_bodies(logger: &RequestLogger) -> bool {
+    logger.enabled && log::log_enabled!(log::Level::Trace)
+}
+
+/// Serializes a JSON body for logging, only if it's going to be logged.
+fn json_body(logger: &RequestLogger, payload: &Value) -> Option<String> {
+    logs_bodies(logger).then(|| payload.to_string())
+}
+
 #[sync_impl]
 impl BaseHttpClient for UreqClient {
     type Error = UreqError;
@@ -101,14 +159,36 @@ impl BaseHttpClient for UreqClient {
         headers: Option<&Headers>,
         payload: &Query,
     ) -> Result<String, Self::Error> {
-        let request = ureq::get(url);
-        let sender = |mut req: Request| {
-            for (key, val) in payload.iter() {
-                req = req.query(key, val);
+        let mut request = ureq::get(url);
+        for (key, val) in payload.iter() {
+            request = request.query(key, val);
+        }
+        let sender = |req: Request| req.call();
+        self.request(request, headers, None, sender)
+    }
+
+    fn get_bytes(&self, url: &str, headers: Option<&Headers>) -> Result<Vec<u8>, Self::Error> {
+        let mut request = ureq::get(url);
+        if let Some(headers) = headers {
+            for (key, val) in headers.iter() {
+                request = request.set(key, val);
             }
-            req.call()
-        };
-        self.request(request, headers, sender)
+        }
+
+        self.logger.request("GET", url, None);
+        match request.call() {
+            Ok(response) => {
+                self.logger.response("GET", url, response.status(), None);
+                let mut bytes = Vec::new();
+                response.into_reader().read_to_end(&mut bytes)?;
+                Ok(bytes)
+            }
+            Err(ureq::Error::Status(status, response)) => {
+                self.logger.response("GET", url, status, None);
+                Err(UreqError::StatusCode(response))
+            }
+            Err(ureq::Error::Transport(transport)) => Err(UreqError::Transport(transport)),
+        }
     }
 
     #[inline]
@@ -120,7 +200,7 @@ impl BaseHttpClient for UreqClient {
     ) -> Result<String, Self::Error> {
         let request = ureq::post(url);
         let sender = |req: Request| req.send_json(payload.clone());
-        self.request(request, headers, sender)
+        self.request(request, headers, json_body(&self.logger, payload), sender)
     }
 
     #[inline]
@@ -139,8 +219,15 @@ impl BaseHttpClient for UreqClient {
 
             req.send_form(&payload)
         };
+        let body = logs_bodies(&self.logger).then(|| {
+            payload
+                .iter()
+                .map(|(key, val)| format!("{key}={val}"))
+                .collect::<Vec<_>>()
+                .join("&")
+        });
 
-        self.request(request, headers, sender)
+        self.request(request, headers, body, sender)
     }
 
     #[inline]
@@ -152,7 +239,7 @@ impl BaseHttpClient for UreqClient {
     ) -> Result<String, Self::Error> {
         let request = ureq::put(url);
         let sender = |req: Request| req.send_json(payload.clone());
-        self.request(request, headers, sender)
+        self.request(request, headers, json_body(&self.logger, payload), sender)
     }
 
     #[inline]
@@ -164,6 +251,6 @@ impl BaseHttpClient for UreqClient {
     ) -> Result<String, Self::Error> {
         let request = ureq::delete(url);
         let sender = |req: Request| req.send_json(payload.clone());
-        self.request(request, headers, sender)
+        self.request(request, headers, json_body(&self.logger, payload), sender)
     }
 }
@@ -27,7 +27,7 @@ pub struct SimplifiedAlbum {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub release_date: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub release_date_precision: Option<String>,
+    pub release_date_precision: Option<DatePrecision>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub restrictions: Option<Restriction>,
 }
@@ -39,6 +39,7 @@ pub struct FullAlbum {
     pub album_type: AlbumType,
     pub available_markets: Option<Vec<String>>,
     pub copyrights: Vec<Copyright>,
+    #[serde(default)]
     pub external_ids: HashMap<String, String>,
     pub external_urls: HashMap<String, String>,
     pub genres: Vec<String>,
@@ -46,6 +47,7 @@ pub struct FullAlbum {
     pub id: AlbumId<'static>,
     pub images: Vec<Image>,
     pub name: String,
+    #[serde(default)]
     pub popularity: u32,
     pub release_date: String,
     pub release_date_precision: DatePrecision,
@@ -54,20 +56,46 @@ pub struct FullAlbum {
     pub label: Option<String>,
 }
 
+impl From<FullAlbum> for SimplifiedAlbum {
+    /// Drops the tracks, copyrights, genres and the rest of the fields only
+    /// available in full albums. `album_group` is left empty, since it only
+    /// makes sense for the albums of an artist.
+    fn from(album: FullAlbum) -> Self {
+        let album_type: &'static str = album.album_type.into();
+        SimplifiedAlbum {
+            album_group: None,
+            album_type: Some(album_type.to_owned()),
+            artists: album.artists,
+            available_markets: album.available_markets.unwrap_or_default(),
+            external_urls: album.external_urls,
+            href: Some(album.href),
+            id: Some(album.id),
+            images: album.images,
+            name: album.name,
+            release_date: Some(album.release_date),
+            release_date_precision: Some(album.release_date_precision),
+            restrictions: None,
+        }
+    }
+}
+
 /// Intermediate full Albums wrapped by Vec object
 #[derive(Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct FullAlbums {
     pub albums: Vec<FullAlbum>,
 }
 
 /// Intermediate simplified Albums wrapped by Page object
 #[derive(Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct PageSimplifiedAlbums {
     pub albums: Page<SimplifiedAlbum>,
 }
 
 /// Saved Album object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct SavedAlbum {
     pub added_at: DateTime<Utc>,
     pub album: FullAlbum,
@@ -75,6 +103,31 @@ pub struct SavedAlbum {
 
 /// Album restriction object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Restriction {
     pub reason: RestrictionReason,
 }
+
+/// Objects that may be unplayable or restricted, like tracks and episodes, so
+/// that whether they can be played is read the same way.
+pub trait WithRestrictions {
+    /// Whether the object isn't explicitly marked as unplayable, i.e., its
+    /// `is_playable` field.
+    fn marked_playable(&self) -> bool;
+
+    /// The restrictions applied to the object, if any.
+    fn restrictions(&self) -> Option<&Restriction>;
+
+    /// Whether the object can be played, i.e., it isn't marked as unplayable
+    /// and it has no restrictions.
+    #[must_use]
+    fn can_play(&self) -> bool {
+        self.marked_playable() && self.restrictions().is_none()
+    }
+
+    /// The reason why the object is restricted, if any.
+    #[must_use]
+    fn restriction_reason(&self) -> Option<&RestrictionReason> {
+        self.restrictions().map(|r| &r.reason)
+    }
+}
@@ -4,7 +4,10 @@ use serde::{Deserialize, Serialize};
 
 use std::collections::HashMap;
 
-use crate::{ArtistId, CursorBasedPage, Followers, Image};
+use crate::{
+    ArtistId, CursorBasedPage, Followers, FullAlbum, FullTrack, Image, SimplifiedAlbum,
+    SimplifiedTrack,
+};
 
 /// Simplified Artist Object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
@@ -19,23 +22,110 @@ pub struct SimplifiedArtist {
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct FullArtist {
     pub external_urls: HashMap<String, String>,
-    pub followers: Followers,
+    pub followers: Option<Followers>,
     pub genres: Vec<String>,
     pub href: String,
     pub id: ArtistId<'static>,
     pub images: Vec<Image>,
     pub name: String,
+    #[serde(default)]
     pub popularity: u32,
 }
 
+impl From<FullArtist> for SimplifiedArtist {
+    /// Keeps the name, ID and links of the artist.
+    fn from(artist: FullArtist) -> Self {
+        SimplifiedArtist {
+            external_urls: artist.external_urls,
+            href: Some(artist.href),
+            id: Some(artist.id),
+            name: artist.name,
+        }
+    }
+}
+
+/// Joins the names of the artists for display, separated by commas and with
+/// an ampersand before the last one, e.g., `Artist A, Artist B & Artist C`.
+///
+/// ```
+/// use rspotify_model::{artist_names, SimplifiedArtist};
+///
+/// let artist = |name: &str| SimplifiedArtist {
+///     name: name.to_owned(),
+///     ..Default::default()
+/// };
+/// assert_eq!(artist_names(&[artist("A")]), "A");
+/// assert_eq!(artist_names(&[artist("A"), artist("B")]), "A & B");
+/// assert_eq!(artist_names(&[artist("A"), artist("B"), artist("C")]), "A, B & C");
+/// ```
+#[must_use]
+pub fn artist_names(artists: &[SimplifiedArtist]) -> String {
+    match artists {
+        [] => String::new(),
+        [artist] => artist.name.clone(),
+        [rest @ .., last] => {
+            let rest = rest
+                .iter()
+                .map(|artist| artist.name.as_str())
+                .collect::<Vec<_>>();
+            format!("{} & {}", rest.join(", "), last.name)
+        }
+    }
+}
+
+/// The IDs of the artists, skipping those without one, such as the artists of
+/// local tracks.
+#[must_use]
+pub fn artist_ids(artists: &[SimplifiedArtist]) -> Vec<ArtistId<'_>> {
+    artists
+        .iter()
+        .filter_map(|artist| artist.id.as_ref().map(ArtistId::as_ref))
+        .collect()
+}
+
+/// Objects credited to a list of artists, like tracks and albums, so that
+/// their names and IDs can be read the same way.
+pub trait WithArtists {
+    /// The artists the object is credited to.
+    fn artists(&self) -> &[SimplifiedArtist];
+
+    /// The names of the artists, joined for display. See [`artist_names`].
+    #[must_use]
+    fn artist_names(&self) -> String {
+        artist_names(self.artists())
+    }
+
+    /// The IDs of the artists. See [`artist_ids`].
+    #[must_use]
+    fn artist_ids(&self) -> Vec<ArtistId<'_>> {
+        artist_ids(self.artists())
+    }
+}
+
+macro_rules! impl_with_artists {
+    ($($object:ty),+ $(,)?) => {
+        $(
+            impl WithArtists for $object {
+                fn artists(&self) -> &[SimplifiedArtist] {
+                    &self.artists
+                }
+            }
+        )+
+    };
+}
+
+impl_with_artists!(FullAlbum, SimplifiedAlbum, FullTrack, SimplifiedTrack);
+
 /// Intermediate full artist object wrapped by `Vec`
 #[derive(Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct FullArtists {
     pub artists: Vec<FullArtist>,
 }
 
 /// Intermediate full Artists vector wrapped by cursor-based-page object
 #[derive(Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct CursorPageFullArtists {
     pub artists: CursorBasedPage<FullArtist>,
 }
@@ -32,14 +32,67 @@ pub struct AudioFeatures {
     pub valence: f32,
 }
 
-/// Intermediate audio feature object wrapped by `Vec`
+/// The number of values in [`AudioFeatures::to_vector`].
+pub const AUDIO_FEATURES_VECTOR_LEN: usize = 9;
+
+// The ranges of tempo and loudness that are normalized into `[0, 1]` by
+// `AudioFeatures::to_vector`, which cover virtually every track.
+const MAX_TEMPO_BPM: f32 = 250.0;
+const MIN_LOUDNESS_DB: f32 = -60.0;
+
+impl AudioFeatures {
+    /// The names of the values in [`Self::to_vector`], in the same order.
+    #[must_use]
+    pub fn feature_names() -> &'static [&'static str; AUDIO_FEATURES_VECTOR_LEN] {
+        &[
+            "danceability",
+            "energy",
+            "valence",
+            "acousticness",
+            "instrumentalness",
+            "liveness",
+            "speechiness",
+            "tempo",
+            "loudness",
+        ]
+    }
+
+    /// Exports the numeric features as a vector in which every value is
+    /// between 0 and 1, e.g., for clustering. The order is given by
+    /// [`Self::feature_names`], and it won't change, so that the vectors are
+    /// comparable across versions.
+    ///
+    /// The tempo is normalized from 0 to 250 BPM, and the loudness from -60 to
+    /// 0 dB; the values outside these ranges are clamped.
+    #[must_use]
+    pub fn to_vector(&self) -> [f32; AUDIO_FEATURES_VECTOR_LEN] {
+        let tempo = (self.tempo / MAX_TEMPO_BPM).clamp(0.0, 1.0);
+        let loudness = (1.0 - self.loudness / MIN_LOUDNESS_DB).clamp(0.0, 1.0);
+        [
+            self.danceability,
+            self.energy,
+            self.valence,
+            self.acousticness,
+            self.instrumentalness,
+            self.liveness,
+            self.speechiness,
+            tempo,
+            loudness,
+        ]
+    }
+}
+
+/// Intermediate audio feature object wrapped by `Vec`. The entries are `None`
+/// for the tracks that don't have audio features.
 #[derive(Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct AudioFeaturesPayload {
-    pub audio_features: Vec<AudioFeatures>,
+    pub audio_features: Vec<Option<AudioFeatures>>,
 }
 
 /// Audio analysis object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct AudioAnalysis {
     pub bars: Vec<TimeInterval>,
     pub beats: Vec<TimeInterval>,
@@ -77,6 +130,7 @@ pub struct AudioAnalysisSection {
 
 /// Audio analysis meta object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Default)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct AudioAnalysisMeta {
     pub analyzer_version: String,
     pub platform: String,
@@ -102,6 +156,7 @@ pub struct AudioAnalysisSegment {
 
 /// Audio analysis track object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct AudioAnalysisTrack {
     pub num_samples: u32,
     pub duration: f32,
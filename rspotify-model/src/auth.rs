@@ -1,8 +1,8 @@
 //! All objects related to the auth flows defined by Spotify API
 
 use crate::{
-    custom_serde::{duration_second, space_separated_scopes},
-    ModelResult,
+    custom_serde::{duration_second, option_expiration, space_separated_scopes},
+    ModelResult, Scope,
 };
 
 use std::{
@@ -27,6 +27,7 @@ pub struct Token {
     pub expires_in: Duration,
     /// The valid time for which the access token is available represented
     /// in ISO 8601 combined date and time.
+    #[serde(default, deserialize_with = "option_expiration::deserialize")]
     pub expires_at: Option<DateTime<Utc>>,
     /// A token that can be sent to the Spotify Accounts service
     /// in place of an authorization code
@@ -42,6 +43,12 @@ pub struct Token {
     pub scopes: HashSet<String>,
 }
 
+/// The name of [`Token`] before it was unified with the other token
+/// representations. Tokens cached under this name can still be loaded with
+/// [`Token::from_cache`].
+#[deprecated(note = "`TokenInfo` has been renamed to `Token`")]
+pub type TokenInfo = Token;
+
 impl Default for Token {
     fn default() -> Self {
         Self {
@@ -85,6 +92,21 @@ impl Token {
         })
     }
 
+    /// Parses the scopes granted for this token. Unknown scopes are ignored.
+    #[must_use]
+    pub fn scopes(&self) -> HashSet<Scope> {
+        self.scopes
+            .iter()
+            .filter_map(|scope| scope.parse().ok())
+            .collect()
+    }
+
+    /// Check if the given scope has been granted for this token.
+    #[must_use]
+    pub fn has_scope(&self, scope: Scope) -> bool {
+        self.scopes.contains(<&str>::from(scope))
+    }
+
     /// Generates an HTTP token authorization header with proper formatting
     #[must_use]
     pub fn auth_headers(&self) -> HashMap<String, String> {
@@ -99,7 +121,8 @@ impl Token {
 
 #[cfg(test)]
 mod test {
-    use crate::Token;
+    use crate::{Scope, Token};
+    use chrono::Duration;
 
     #[test]
     fn test_bearer_auth() {
@@ -115,4 +138,51 @@ mod test {
             Some(&"Bearer access_token".to_owned())
         );
     }
+
+    #[test]
+    fn test_scopes() {
+        let tok = Token {
+            scopes: ["user-read-private", "streaming", "not-a-scope"]
+                .iter()
+                .map(|scope| scope.to_string())
+                .collect(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            tok.scopes(),
+            [Scope::UserReadPrivate, Scope::Streaming]
+                .iter()
+                .copied()
+                .collect()
+        );
+        assert!(tok.has_scope(Scope::Streaming));
+        assert!(!tok.has_scope(Scope::UserModifyPlaybackState));
+    }
+
+    #[test]
+    fn test_legacy_token_info() {
+        // Format written by the old `TokenInfo`, with a `token_type` field and
+        // the expiration as a Unix timestamp
+        let json = r#"
+        {
+            "access_token": "access_token",
+            "token_type": "Bearer",
+            "expires_in": 3600,
+            "expires_at": 1633000000,
+            "refresh_token": "refresh_token",
+            "scope": "user-read-private streaming"
+        }
+        "#;
+        let tok: Token = serde_json::from_str(json).unwrap();
+        assert_eq!(tok.expires_in, Duration::seconds(3600));
+        assert_eq!(tok.expires_at.unwrap().timestamp(), 1633000000);
+        assert_eq!(tok.refresh_token.as_deref(), Some("refresh_token"));
+        assert!(tok.has_scope(Scope::Streaming));
+
+        // Once written again it uses the current format, which also loads
+        let json = serde_json::to_string(&tok).unwrap();
+        assert!(json.contains("2021-09-30T11:06:40Z"));
+        assert_eq!(serde_json::from_str::<Token>(&json).unwrap(), tok);
+    }
 }
@@ -6,6 +6,7 @@ use crate::{Image, Page};
 
 /// Category object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Category {
     pub href: String,
     pub icons: Vec<Image>,
@@ -15,6 +16,7 @@ pub struct Category {
 
 /// Intermediate categories wrapped by page object
 #[derive(Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct PageCategory {
     pub categories: Page<Category>,
 }
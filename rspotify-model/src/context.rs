@@ -7,12 +7,14 @@ use std::collections::HashMap;
 use std::time::Duration;
 
 use crate::{
-    custom_serde::{millisecond_timestamp, option_duration_ms},
-    CurrentlyPlayingType, Device, DisallowKey, PlayableItem, RepeatState, Type,
+    custom_serde::{duration_ms, millisecond_timestamp, option_duration_ms},
+    AlbumId, ArtistId, CurrentlyPlayingType, Device, DisallowKey, Id, PlayContextId, PlayableItem,
+    PlaylistId, RepeatState, ShowId, Type,
 };
 
 /// Context object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Context {
     /// The URI may be of any type, so it's not parsed into a [`crate::Id`]
     pub uri: String,
@@ -22,8 +24,66 @@ pub struct Context {
     pub _type: Type,
 }
 
+impl Context {
+    /// Parses the URI into the ID of the album, artist, playlist or show being
+    /// played. Returns `None` for other types of context, or if the URI
+    /// doesn't match the type.
+    #[must_use]
+    pub fn play_context_id(&self) -> Option<PlayContextId<'_>> {
+        let id = match self._type {
+            Type::Album => PlayContextId::Album(AlbumId::from_uri(&self.uri).ok()?),
+            Type::Artist => PlayContextId::Artist(ArtistId::from_uri(&self.uri).ok()?),
+            Type::Playlist => PlayContextId::Playlist(PlaylistId::from_uri(&self.uri).ok()?),
+            Type::Show => PlayContextId::Show(ShowId::from_uri(&self.uri).ok()?),
+            _ => return None,
+        };
+        Some(id)
+    }
+
+    /// The ID of the album being played, or `None` if the context is of
+    /// another type.
+    #[must_use]
+    pub fn as_album_id(&self) -> Option<AlbumId<'_>> {
+        match self.play_context_id()? {
+            PlayContextId::Album(id) => Some(id),
+            _ => None,
+        }
+    }
+
+    /// The ID of the artist being played, or `None` if the context is of
+    /// another type.
+    #[must_use]
+    pub fn as_artist_id(&self) -> Option<ArtistId<'_>> {
+        match self.play_context_id()? {
+            PlayContextId::Artist(id) => Some(id),
+            _ => None,
+        }
+    }
+
+    /// The ID of the playlist being played, or `None` if the context is of
+    /// another type.
+    #[must_use]
+    pub fn as_playlist_id(&self) -> Option<PlaylistId<'_>> {
+        match self.play_context_id()? {
+            PlayContextId::Playlist(id) => Some(id),
+            _ => None,
+        }
+    }
+
+    /// The ID of the show being played, or `None` if the context is of
+    /// another type.
+    #[must_use]
+    pub fn as_show_id(&self) -> Option<ShowId<'_>> {
+        match self.play_context_id()? {
+            PlayContextId::Show(id) => Some(id),
+            _ => None,
+        }
+    }
+}
+
 /// Currently playing object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct CurrentlyPlayingContext {
     pub context: Option<Context>,
     #[serde(with = "millisecond_timestamp")]
@@ -38,6 +98,7 @@ pub struct CurrentlyPlayingContext {
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct CurrentPlaybackContext {
     pub device: Device,
     pub repeat_state: RepeatState,
@@ -54,18 +115,91 @@ pub struct CurrentPlaybackContext {
     pub actions: Actions,
 }
 
-/// Actions object
+impl CurrentPlaybackContext {
+    /// A snapshot of what's being played and where, which can be saved to
+    /// resume the playback later. It's `None` if nothing is being played, or
+    /// if the item has no URI, as it happens with local files.
+    #[must_use]
+    pub fn playback_state(&self) -> Option<PlaybackState> {
+        let item_uri = self.item.as_ref()?.id()?.uri();
+        Some(PlaybackState {
+            item_uri,
+            position: self.progress.unwrap_or_default(),
+            device_id: self.device.id.clone(),
+        })
+    }
+}
+
+/// The item being played, its position and the device playing it, obtained
+/// with [`CurrentPlaybackContext::playback_state`].
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PlaybackState {
+    /// The URI of the track or episode
+    pub item_uri: String,
+    #[serde(with = "duration_ms", rename = "position_ms")]
+    pub position: Duration,
+    pub device_id: Option<String>,
+}
+
+/// Actions object, with the actions that are disallowed in the current
+/// playback, e.g., to disable the controls of a player.
 #[derive(Clone, Debug, Serialize, PartialEq, Eq, Default)]
 pub struct Actions {
     pub disallows: Vec<DisallowKey>,
 }
 
+impl Actions {
+    /// Whether the action is allowed, i.e., it's not disallowed.
+    #[must_use]
+    pub fn is_allowed(&self, action: DisallowKey) -> bool {
+        !self.disallows.contains(&action)
+    }
+
+    /// Whether the playback can be paused.
+    #[must_use]
+    pub fn can_pause(&self) -> bool {
+        self.is_allowed(DisallowKey::Pausing)
+    }
+
+    /// Whether the playback can be resumed.
+    #[must_use]
+    pub fn can_resume(&self) -> bool {
+        self.is_allowed(DisallowKey::Resuming)
+    }
+
+    /// Whether the position in the current item can be changed.
+    #[must_use]
+    pub fn can_seek(&self) -> bool {
+        self.is_allowed(DisallowKey::Seeking)
+    }
+
+    /// Whether the playback can skip to the next item, which isn't possible
+    /// at the end of the queue, for example.
+    #[must_use]
+    pub fn can_skip_next(&self) -> bool {
+        self.is_allowed(DisallowKey::SkippingNext)
+    }
+
+    /// Whether the playback can skip to the previous item.
+    #[must_use]
+    pub fn can_skip_prev(&self) -> bool {
+        self.is_allowed(DisallowKey::SkippingPrev)
+    }
+
+    /// Whether shuffle can be toggled.
+    #[must_use]
+    pub fn can_toggle_shuffle(&self) -> bool {
+        self.is_allowed(DisallowKey::TogglingShuffle)
+    }
+}
+
 impl<'de> Deserialize<'de> for Actions {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
         #[derive(Deserialize)]
+        #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
         struct OriginalActions {
             pub disallows: HashMap<DisallowKey, bool>,
         }
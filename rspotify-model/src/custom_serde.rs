@@ -1,6 +1,24 @@
 //! Custom serialization methods used throughout the crate
 
+/// Deserializes a number of milliseconds with the given visitor. Some
+/// proxies turn numbers into strings, so human-readable formats like JSON are
+/// deserialized as any type, and the visitor accepts strings as well. Other
+/// formats, like bincode, aren't self-describing, so a `u64` is deserialized
+/// in that case.
+fn deserialize_millis<'de, D, V>(d: D, visitor: V) -> Result<V::Value, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    V: serde::de::Visitor<'de>,
+{
+    if d.is_human_readable() {
+        d.deserialize_any(visitor)
+    } else {
+        d.deserialize_u64(visitor)
+    }
+}
+
 pub mod duration_ms {
+    use super::deserialize_millis;
     use serde::{de, Serializer};
     use std::{fmt, time::Duration};
 
@@ -24,14 +42,25 @@ pub mod duration_ms {
         {
             Ok(Duration::from_millis(v.max(0) as u64))
         }
+        /// Some proxies turn numbers into strings, which are accepted as well.
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            match v.parse::<u64>() {
+                Ok(v) => self.visit_u64(v),
+                Err(_) => Err(E::invalid_value(de::Unexpected::Str(v), &self)),
+            }
+        }
     }
 
-    /// Deserialize `std::time::Duration` from milliseconds (represented as u64)
+    /// Deserialize `std::time::Duration` from milliseconds (represented as u64
+    /// or as a string)
     pub fn deserialize<'de, D>(d: D) -> Result<Duration, D::Error>
     where
         D: de::Deserializer<'de>,
     {
-        d.deserialize_u64(DurationVisitor)
+        deserialize_millis(d, DurationVisitor)
     }
 
     /// Serialize `std::time::Duration` to milliseconds (represented as u64)
@@ -44,9 +73,10 @@ pub mod duration_ms {
 }
 
 pub mod millisecond_timestamp {
+    use super::deserialize_millis;
     use chrono::{DateTime, NaiveDateTime, Utc};
     use serde::{de, Serializer};
-    use std::fmt;
+    use std::{convert::TryFrom, fmt};
 
     /// Vistor to help deserialize unix millisecond timestamp to
     /// `chrono::DateTime`.
@@ -76,14 +106,36 @@ pub mod millisecond_timestamp {
             );
             Ok(dt)
         }
+
+        /// Some parsers, like `simd-json`, read positive numbers as `i64`.
+        fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            match u64::try_from(v) {
+                Ok(v) => self.visit_u64(v),
+                Err(_) => Err(E::invalid_value(de::Unexpected::Signed(v), &self)),
+            }
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            match v.parse::<u64>() {
+                Ok(v) => self.visit_u64(v),
+                Err(_) => Err(E::invalid_value(de::Unexpected::Str(v), &self)),
+            }
+        }
     }
 
-    /// Deserialize Unix millisecond timestamp to `DateTime<Utc>`
+    /// Deserialize Unix millisecond timestamp (represented as u64 or as a
+    /// string) to `DateTime<Utc>`
     pub fn deserialize<'de, D>(d: D) -> Result<DateTime<Utc>, D::Error>
     where
         D: de::Deserializer<'de>,
     {
-        d.deserialize_u64(DateTimeVisitor)
+        deserialize_millis(d, DateTimeVisitor)
     }
 
     /// Serialize `DateTime<Utc>` to Unix millisecond timestamp
@@ -96,6 +148,7 @@ pub mod millisecond_timestamp {
 }
 
 pub mod option_duration_ms {
+    use super::deserialize_millis;
     use crate::custom_serde::duration_ms;
     use serde::{de, Serializer};
     use std::{fmt, time::Duration};
@@ -125,9 +178,10 @@ pub mod option_duration_ms {
         where
             D: de::Deserializer<'de>,
         {
-            Ok(Some(
-                deserializer.deserialize_u64(duration_ms::DurationVisitor)?,
-            ))
+            Ok(Some(deserialize_millis(
+                deserializer,
+                duration_ms::DurationVisitor,
+            )?))
         }
     }
 
@@ -147,7 +201,7 @@ pub mod option_duration_ms {
         S: Serializer,
     {
         match *x {
-            Some(duration) => s.serialize_u64(duration.as_millis() as u64),
+            Some(duration) => s.serialize_some(&(duration.as_millis() as u64)),
             None => s.serialize_none(),
         }
     }
@@ -228,3 +282,87 @@ pub mod space_separated_scopes {
         s.serialize_str(&scopes)
     }
 }
+
+pub mod option_expiration {
+    use chrono::{DateTime, TimeZone, Utc};
+    use serde::{de, Deserialize};
+
+    /// Either representation of the expiration time. Tokens cached by older
+    /// versions (as `TokenInfo`) stored it as a Unix timestamp in seconds.
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Expiration {
+        DateTime(DateTime<Utc>),
+        Timestamp(i64),
+    }
+
+    /// Deserialize `Option<DateTime<Utc>>` from either an ISO 8601 date or a
+    /// Unix timestamp in seconds
+    pub fn deserialize<'de, D>(d: D) -> Result<Option<DateTime<Utc>>, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let expiration: Option<Expiration> = Deserialize::deserialize(d)?;
+        match expiration {
+            None => Ok(None),
+            Some(Expiration::DateTime(dt)) => Ok(Some(dt)),
+            Some(Expiration::Timestamp(secs)) => Utc
+                .timestamp_opt(secs, 0)
+                .single()
+                .map(Some)
+                .ok_or_else(|| de::Error::custom(format!("invalid timestamp: {}", secs))),
+        }
+    }
+}
+
+pub mod null_as_default {
+    use serde::{de, Deserialize};
+
+    /// Deserialize `T` with its default value when it's `null`
+    pub fn deserialize<'de, D, T>(d: D) -> Result<T, D::Error>
+    where
+        D: de::Deserializer<'de>,
+        T: Deserialize<'de> + Default,
+    {
+        let value: Option<T> = Deserialize::deserialize(d)?;
+        Ok(value.unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{duration_ms, millisecond_timestamp, option_duration_ms};
+    use chrono::{DateTime, Utc};
+    use serde::{Deserialize, Serialize};
+    use std::time::Duration;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Millis {
+        #[serde(with = "duration_ms")]
+        duration: Duration,
+        #[serde(with = "option_duration_ms")]
+        optional_duration: Option<Duration>,
+        #[serde(with = "millisecond_timestamp")]
+        timestamp: DateTime<Utc>,
+    }
+
+    #[test]
+    fn test_millis_bincode() {
+        let json = r#"
+        {
+            "duration": "22270",
+            "optional_duration": 423432,
+            "timestamp": "1607769168429"
+        }
+        "#;
+        let millis: Millis = serde_json::from_str(json).unwrap();
+        assert_eq!(millis.duration, Duration::from_millis(22270));
+        assert_eq!(millis.timestamp.timestamp_millis(), 1607769168429);
+
+        // bincode isn't self-describing, so the milliseconds can't be read as
+        // any type like in JSON
+        let bytes = bincode::serialize(&millis).unwrap();
+        let decoded: Millis = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(decoded, millis);
+    }
+}
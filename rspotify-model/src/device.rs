@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 
 /// Device object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Device {
     pub id: Option<String>,
     pub is_active: bool,
@@ -12,10 +13,28 @@ pub struct Device {
     #[serde(rename = "type")]
     pub _type: DeviceType,
     pub volume_percent: Option<u32>,
+    /// Older responses don't include this field, in which case the volume is
+    /// assumed to be supported.
+    #[serde(default = "supports_volume_by_default")]
+    pub supports_volume: bool,
+}
+
+fn supports_volume_by_default() -> bool {
+    true
+}
+
+impl Device {
+    /// Whether the volume of the device can be set. Restricted devices don't
+    /// accept any commands, so they don't support it either.
+    #[must_use]
+    pub fn can_set_volume(&self) -> bool {
+        self.supports_volume && !self.is_restricted
+    }
 }
 
 /// Intermediate device payload object
 #[derive(Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct DevicePayload {
     pub devices: Vec<Device>,
 }
@@ -36,5 +55,24 @@ fn test_devices() {
         }
 "#;
     let payload: DevicePayload = serde_json::from_str(json_str).unwrap();
-    assert_eq!(payload.devices[0]._type, DeviceType::Computer)
+    assert_eq!(payload.devices[0]._type, DeviceType::Computer);
+    assert!(payload.devices[0].can_set_volume());
+}
+
+#[test]
+fn test_device_without_volume() {
+    let json_str = r#"
+        {
+            "id" : "5fbb3ba6aa454b5534c4ba43a8c7e8e45a63ad0e",
+            "is_active" : true,
+            "is_private_session": false,
+            "is_restricted" : false,
+            "name" : "Living room TV",
+            "type" : "TV",
+            "volume_percent" : null,
+            "supports_volume" : false
+        }
+"#;
+    let device: Device = serde_json::from_str(json_str).unwrap();
+    assert!(!device.can_set_volume());
 }
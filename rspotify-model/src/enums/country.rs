@@ -1,9 +1,9 @@
 use serde::{Deserialize, Serialize};
-use strum::IntoStaticStr;
+use strum::{EnumString, IntoStaticStr};
 
 /// ISO 3166-1 alpha-2 country code, from
 /// [country-list](https://datahub.io/core/country-list)
-#[derive(Clone, Serialize, Deserialize, Copy, PartialEq, Eq, Debug, IntoStaticStr)]
+#[derive(Clone, Serialize, Deserialize, Copy, PartialEq, Eq, Debug, EnumString, IntoStaticStr)]
 pub enum Country {
     #[strum(serialize = "AF")]
     #[serde(rename = "AF")]
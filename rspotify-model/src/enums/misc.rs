@@ -1,6 +1,6 @@
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
-use strum::IntoStaticStr;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use strum::{Display, EnumString, IntoStaticStr};
 
 use super::Country;
 
@@ -43,7 +43,9 @@ pub enum RepeatState {
     Context,
 }
 
-/// Type for `include_external`: `audio`.
+/// Type for `include_external`: `audio`, which signals that the client can
+/// play externally hosted audio content, so that it's marked as playable in
+/// search results.
 #[derive(Clone, Serialize, Deserialize, Copy, PartialEq, Eq, Debug, IntoStaticStr)]
 #[serde(rename_all = "snake_case")]
 #[strum(serialize_all = "snake_case")]
@@ -52,23 +54,102 @@ pub enum IncludeExternal {
 }
 
 /// Date precision: `year`, `month`, `day`.
-#[derive(Clone, Serialize, Deserialize, Copy, PartialEq, Eq, Debug, IntoStaticStr)]
-#[serde(rename_all = "snake_case")]
+///
+/// Any other precision is parsed as `Unknown` instead of failing.
+#[derive(Clone, PartialEq, Eq, Debug, IntoStaticStr)]
 #[strum(serialize_all = "snake_case")]
 pub enum DatePrecision {
     Year,
     Month,
     Day,
+    Unknown(String),
+}
+
+impl DatePrecision {
+    /// The precision as sent by Spotify, including the unknown ones.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            DatePrecision::Year => "year",
+            DatePrecision::Month => "month",
+            DatePrecision::Day => "day",
+            DatePrecision::Unknown(precision) => precision,
+        }
+    }
+}
+
+impl Serialize for DatePrecision {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        s.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for DatePrecision {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let precision = String::deserialize(d)?;
+        Ok(match precision.as_str() {
+            "year" => DatePrecision::Year,
+            "month" => DatePrecision::Month,
+            "day" => DatePrecision::Day,
+            _ => DatePrecision::Unknown(precision),
+        })
+    }
 }
 
 /// The reason for the restriction: `market`, `product`, `explicit`
-#[derive(Clone, Serialize, Deserialize, Copy, PartialEq, Eq, Debug, IntoStaticStr)]
-#[serde(rename_all = "snake_case")]
+///
+/// Any other reason Spotify may add in the future is parsed as `Unknown`
+/// instead of failing.
+#[derive(Clone, PartialEq, Eq, Debug, IntoStaticStr)]
 #[strum(serialize_all = "snake_case")]
 pub enum RestrictionReason {
     Market,
     Product,
     Explicit,
+    Unknown(String),
+}
+
+impl RestrictionReason {
+    /// The reason as sent by Spotify, including the unknown ones.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            RestrictionReason::Market => "market",
+            RestrictionReason::Product => "product",
+            RestrictionReason::Explicit => "explicit",
+            RestrictionReason::Unknown(reason) => reason,
+        }
+    }
+}
+
+impl Serialize for RestrictionReason {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        s.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for RestrictionReason {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let reason = String::deserialize(d)?;
+        Ok(match reason.as_str() {
+            "market" => RestrictionReason::Market,
+            "product" => RestrictionReason::Product,
+            "explicit" => RestrictionReason::Explicit,
+            _ => RestrictionReason::Unknown(reason),
+        })
+    }
 }
 
 /// Indicates the modality (major or minor) of a track.
@@ -107,3 +188,42 @@ pub enum TimeLimits {
     Before(DateTime<Utc>),
     After(DateTime<Utc>),
 }
+
+/// Authorization [scopes](https://developer.spotify.com/documentation/general/guides/authorization/scopes/),
+/// such as `user-read-private` or `playlist-modify-public`.
+#[derive(
+    Clone,
+    Serialize,
+    Deserialize,
+    Copy,
+    PartialEq,
+    Eq,
+    Debug,
+    Hash,
+    Display,
+    EnumString,
+    IntoStaticStr,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum Scope {
+    UgcImageUpload,
+    UserReadPlaybackState,
+    UserModifyPlaybackState,
+    UserReadCurrentlyPlaying,
+    AppRemoteControl,
+    Streaming,
+    PlaylistReadPrivate,
+    PlaylistReadCollaborative,
+    PlaylistModifyPrivate,
+    PlaylistModifyPublic,
+    UserFollowModify,
+    UserFollowRead,
+    UserReadPlaybackPosition,
+    UserTopRead,
+    UserReadRecentlyPlayed,
+    UserLibraryModify,
+    UserLibraryRead,
+    UserReadEmail,
+    UserReadPrivate,
+}
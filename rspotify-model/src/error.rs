@@ -24,11 +24,21 @@ pub enum ApiError {
 }
 
 /// Groups up the kinds of errors that may happen in this crate.
+///
+/// New variants may be added in minor releases, so matching on it requires a
+/// wildcard arm.
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum ModelError {
     #[error("json parse error: {0}")]
     ParseJson(#[from] serde_json::Error),
 
     #[error("input/output error: {0}")]
     Io(#[from] std::io::Error),
+
+    #[error("invalid offset: {0}")]
+    InvalidOffset(String),
+
+    #[error("`{0}` is not an available genre seed")]
+    InvalidGenreSeed(String),
 }
@@ -78,6 +78,30 @@
 //! ];
 //!
 
+//! ```
+//!
+//! ## Owned IDs
+//!
+//! IDs parsed from a borrowed string keep its lifetime. In order to store them
+//! after the string is dropped, they can be converted into their owned
+//! version, with a `'static` lifetime, by value with `into_static` or by
+//! reference with `clone_static`. For a whole collection:
+//!
+//! ```
+//! use rspotify_model::TrackId;
+//!
+//! fn parse_ids(input: &str) -> Vec<TrackId<'static>> {
+//!     input
+//!         .lines()
+//!         .filter_map(|line| TrackId::from_id_or_uri(line).ok())
+//!         .map(TrackId::into_static)
+//!         .collect()
+//! }
+//!
+//! let buffer = String::from("4iV5W9uYEdYUVa79Axb7Rh\nspotify:track:5iKndSu1XI74U2OZePzP8L");
+//! let ids = parse_ids(&buffer);
+//! drop(buffer);
+//! assert_eq!(ids.len(), 2);
 //! ```
 
 use enum_dispatch::enum_dispatch;
@@ -85,7 +109,7 @@ use serde::{Deserialize, Serialize};
 use strum::Display;
 use thiserror::Error;
 
-use std::{borrow::Cow, fmt::Debug, hash::Hash};
+use std::{borrow::Cow, convert::TryFrom, fmt::Debug, hash::Hash};
 
 use crate::Type;
 
@@ -135,6 +159,42 @@ pub trait Id {
     fn url(&self) -> String {
         format!("https://open.spotify.com/{}/{}", self._type(), self.id())
     }
+
+    /// Returns a full Spotify object URL with a locale, which is either a
+    /// language code (`xx`) or a language and a region (`xx-YY`). Returns
+    /// `None` if the locale doesn't have that format.
+    ///
+    /// Examples: `https://open.spotify.com/es/track/4y4VO05kYgUTo2bzbox1an`,
+    /// `https://open.spotify.com/pt-BR/artist/2QI8e2Vwgg9KXOz2zjcrkI`.
+    fn url_localized(&self, locale: &str) -> Option<String> {
+        let is_lang = |s: &str| s.len() == 2 && s.bytes().all(|c| c.is_ascii_lowercase());
+        let is_region = |s: &str| s.len() == 2 && s.bytes().all(|c| c.is_ascii_uppercase());
+        let valid = match locale.split_once('-') {
+            Some((lang, region)) => is_lang(lang) && is_region(region),
+            None => is_lang(locale),
+        };
+
+        valid.then(|| {
+            format!(
+                "https://open.spotify.com/{}/{}/{}",
+                locale,
+                self._type(),
+                self.id()
+            )
+        })
+    }
+}
+
+/// References to IDs can be used where an ID is expected, e.g., to pass the
+/// items of a slice without cloning them.
+impl<T: Id + ?Sized> Id for &T {
+    fn id(&self) -> &str {
+        (**self).id()
+    }
+
+    fn _type(&self) -> Type {
+        (**self)._type()
+    }
 }
 
 /// A lower level function to parse a URI into both its type and its actual ID.
@@ -167,6 +227,73 @@ pub fn parse_uri(uri: &str) -> Result<(Type, &str), IdError> {
     }
 }
 
+/// Extracts the ID of the object an API URL points to, such as the `href` of
+/// the objects and pages in the responses. When the URL points to a list
+/// within an object, like `shows/{id}/episodes`, the ID of that object is
+/// returned. Returns `None` if the URL doesn't include any ID.
+///
+/// The result can be turned into a typed ID with `from_id`:
+///
+/// ```
+/// use rspotify_model::{idtypes::id_from_href, ShowId};
+///
+/// let href = "https://api.spotify.com/v1/shows/38bS44xjbVVZ3No3ByF1dJ/episodes?offset=0&limit=50";
+/// let show_id = id_from_href(href).map(ShowId::from_id);
+/// assert_eq!(show_id, Some(ShowId::from_id("38bS44xjbVVZ3No3ByF1dJ")));
+/// ```
+#[must_use]
+pub fn id_from_href(href: &str) -> Option<&str> {
+    const COLLECTIONS: &[&str] = &[
+        "albums",
+        "artists",
+        "audiobooks",
+        "chapters",
+        "episodes",
+        "playlists",
+        "shows",
+        "tracks",
+        "users",
+    ];
+
+    let path = href.split(['?', '#']).next().unwrap_or(href);
+    let segments = path.split('/').collect::<Vec<_>>();
+    segments
+        .windows(2)
+        .filter(|pair| COLLECTIONS.contains(&pair[0]) && !pair[1].is_empty())
+        .map(|pair| pair[1])
+        .rfind(|segment| !COLLECTIONS.contains(segment))
+}
+
+/// Parses a list of IDs or URIs of the same type with `T::try_from`, which is
+/// equivalent to `from_id_or_uri`. Instead of stopping at the first invalid
+/// input, it returns both the parsed IDs and the inputs that failed along with
+/// their error, in their original order.
+///
+/// ```
+/// use rspotify_model::{idtypes::parse_ids, IdError, TrackId};
+///
+/// let lines = "spotify:track:4iV5W9uYEdYUVa79Axb7Rh\nnot an id\n5iKndSu1XI74U2OZePzP8L";
+/// let (ids, failed) = parse_ids::<TrackId>(lines.lines());
+/// assert_eq!(ids.len(), 2);
+/// assert_eq!(failed, vec![("not an id".to_owned(), IdError::InvalidId)]);
+/// ```
+pub fn parse_ids<'a, T>(
+    inputs: impl IntoIterator<Item = &'a str>,
+) -> (Vec<T>, Vec<(String, IdError)>)
+where
+    T: TryFrom<&'a str, Error = IdError>,
+{
+    let mut ids = Vec::new();
+    let mut failed = Vec::new();
+    for input in inputs {
+        match T::try_from(input) {
+            Ok(id) => ids.push(id),
+            Err(err) => failed.push((input.to_owned(), err)),
+        }
+    }
+    (ids, failed)
+}
+
 /// This macro helps consistently define ID types.
 ///
 /// * The `$type` parameter indicates what variant in `Type` the ID is for (say,
@@ -186,9 +313,17 @@ macro_rules! define_idtypes {
                 docs][`crate::idtypes`] for more information. "
             )]
             #[repr(transparent)]
-            #[derive(Clone, Debug, PartialEq, Eq, Serialize, Hash)]
+            #[derive(Clone, Debug, Eq, Serialize, Hash)]
             pub struct $name<'a>(Cow<'a, str>);
 
+            /// IDs are compared by their string, so borrowed and owned ones
+            /// with different lifetimes can be compared directly.
+            impl<'a, 'b> PartialEq<$name<'b>> for $name<'a> {
+                fn eq(&self, other: &$name<'b>) -> bool {
+                    self.0 == other.0
+                }
+            }
+
             impl<'a> $name<'a> {
                 /// The type of the ID, as a constant.
                 const TYPE: Type = Type::$type;
@@ -339,6 +474,34 @@ macro_rules! define_idtypes {
                 pub fn clone_static(&self) -> $name<'static> {
                     $name(Cow::Owned(self.0.clone().into_owned()))
                 }
+
+                /// Reinterprets the ID as one of a different type, without
+                /// copying it or going through its URI. This is for advanced
+                /// use only, such as legacy data with ambiguous types: nothing
+                /// guarantees that the object exists with the new type.
+                ///
+                /// The characters are still checked with the validity
+                /// function of the new type, since some IDs accept characters
+                /// that others don't.
+                ///
+                /// ```
+                /// use rspotify_model::{AlbumId, TrackId};
+                ///
+                /// let track_id = TrackId::from_id("4iV5W9uYEdYUVa79Axb7Rh").unwrap();
+                /// let album_id: AlbumId = track_id.cast().unwrap();
+                /// assert_eq!(album_id.to_string(), "spotify:album:4iV5W9uYEdYUVa79Axb7Rh");
+                /// ```
+                ///
+                /// # Errors
+                ///
+                /// - `IdError::InvalidId` - if the ID isn't valid for the new
+                ///   type.
+                pub fn cast<T>(self) -> Result<T, IdError>
+                where
+                    T: TryFrom<Cow<'a, str>, Error = IdError>,
+                {
+                    T::try_from(self.0)
+                }
             }
 
             impl Id for $name<'_> {
@@ -351,6 +514,36 @@ macro_rules! define_idtypes {
                 }
             }
 
+            /// Equivalent to [`Self::from_id_or_uri`], useful for generic code
+            /// and for other libraries, such as argument parsers.
+            impl<'a> TryFrom<&'a str> for $name<'a> {
+                type Error = IdError;
+
+                fn try_from(id_or_uri: &'a str) -> Result<Self, Self::Error> {
+                    Self::from_id_or_uri(id_or_uri)
+                }
+            }
+
+            /// Equivalent to [`Self::from_id`], so it only accepts IDs, not
+            /// URIs. Useful for generic code, such as [`Self::cast`].
+            impl<'a> TryFrom<Cow<'a, str>> for $name<'a> {
+                type Error = IdError;
+
+                fn try_from(id: Cow<'a, str>) -> Result<Self, Self::Error> {
+                    Self::from_id(id)
+                }
+            }
+
+            /// Equivalent to [`Self::from_id_or_uri`], but the resulting ID
+            /// is owned.
+            impl TryFrom<String> for $name<'static> {
+                type Error = IdError;
+
+                fn try_from(id_or_uri: String) -> Result<Self, Self::Error> {
+                    $name::from_id_or_uri(&id_or_uri).map($name::into_static)
+                }
+            }
+
             // Deserialization may take either an ID or an URI, so its
             // implementation has to be done manually.
             impl<'de> Deserialize<'de> for $name<'static> {
@@ -410,7 +603,9 @@ macro_rules! define_idtypes {
             }
 
             /// `Id`s may be borrowed as `str` the same way `Box<T>` may be
-            /// borrowed as `T` or `String` as `str`
+            /// borrowed as `T` or `String` as `str`. Their `Hash` and `Eq`
+            /// only take the ID string into account, so the IDs in a
+            /// `HashSet` or the keys of a `HashMap` can be looked up by `&str`.
             impl std::borrow::Borrow<str> for $name<'_> {
                 fn borrow(&self) -> &str {
                     self.id()
@@ -578,6 +773,79 @@ mod test {
     const URI_MIXED1: &str = "spotify/track:4iV5W9uYEdYUVa79Axb7Rh";
     const URI_MIXED2: &str = "spotify:track/4iV5W9uYEdYUVa79Axb7Rh";
 
+    #[test]
+    fn test_try_from() {
+        assert_eq!(TrackId::try_from(ID), TrackId::from_id(ID));
+        assert_eq!(TrackId::try_from(URI), TrackId::from_id(ID));
+        assert_eq!(TrackId::try_from(URI.to_owned()), TrackId::from_id(ID));
+        assert_eq!(TrackId::try_from(URI_EMPTY), Err(IdError::InvalidType));
+        assert_eq!(AlbumId::try_from(URI.to_owned()), Err(IdError::InvalidType));
+    }
+
+    #[test]
+    fn test_parse_ids() {
+        let inputs = [
+            ID,
+            URI_EMPTY,
+            URI_SLASHES,
+            "spotify:album:4iV5W9uYEdYUVa79Axb7Rh",
+        ];
+        let (ids, failed) = parse_ids::<TrackId>(inputs);
+        assert_eq!(ids, vec![TrackId::from_id(ID).unwrap(); 2]);
+        assert_eq!(
+            failed,
+            vec![
+                (URI_EMPTY.to_owned(), IdError::InvalidType),
+                (
+                    "spotify:album:4iV5W9uYEdYUVa79Axb7Rh".to_owned(),
+                    IdError::InvalidType
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cast() {
+        let track_id = TrackId::from_id(ID).unwrap();
+        let album_id: AlbumId = track_id.clone().cast().unwrap();
+        assert_eq!(album_id.id(), ID);
+        assert_eq!(album_id.uri(), format!("spotify:album:{ID}"));
+
+        // User IDs may contain characters that aren't valid in other IDs
+        let user_id = UserId::from_id("user.name").unwrap();
+        assert_eq!(user_id.cast::<TrackId>(), Err(IdError::InvalidId));
+        assert!(track_id.cast::<UserId>().is_ok());
+    }
+
+    #[test]
+    fn test_borrow_str() {
+        use std::collections::{HashMap, HashSet};
+
+        let ids = HashSet::from([TrackId::from_uri(URI).unwrap()]);
+        assert!(ids.contains(ID));
+        assert!(!ids.contains("5iKndSu1XI74U2OZePzP8L"));
+
+        let names = HashMap::from([(TrackId::from_id(ID).unwrap().into_static(), "name")]);
+        assert_eq!(names.get(ID), Some(&"name"));
+    }
+
+    #[test]
+    fn test_url_localized() {
+        let id = TrackId::from_id(ID).unwrap();
+        assert_eq!(
+            id.url_localized("es").unwrap(),
+            "https://open.spotify.com/es/track/4iV5W9uYEdYUVa79Axb7Rh"
+        );
+        assert_eq!(
+            id.url_localized("pt-BR").unwrap(),
+            "https://open.spotify.com/pt-BR/track/4iV5W9uYEdYUVa79Axb7Rh"
+        );
+        assert_eq!(id.url_localized(""), None);
+        assert_eq!(id.url_localized("ES"), None);
+        assert_eq!(id.url_localized("pt-br"), None);
+        assert_eq!(id.url_localized("es/../"), None);
+    }
+
     #[test]
     fn test_id_parse() {
         assert!(TrackId::from_id(ID).is_ok());
@@ -684,6 +952,39 @@ mod test {
         let _ = EpisodeId::from_id(Cow::Owned(ID.to_string())).unwrap();
     }
 
+    #[test]
+    fn test_eq_borrowed_owned() {
+        let local_id = String::from(ID);
+        let borrowed = EpisodeId::from_id(local_id.as_str()).unwrap();
+        let owned: EpisodeId<'static> = EpisodeId::from_id(ID.to_string()).unwrap();
+        assert_eq!(borrowed, owned);
+        assert_eq!(owned, borrowed);
+
+        // Also when the lifetimes can't be unified, like with mutable
+        // references
+        fn same<'a, 'b>(a: &mut EpisodeId<'a>, b: &mut EpisodeId<'b>) -> bool {
+            a == b
+        }
+        assert!(same(&mut borrowed.clone(), &mut owned.clone()));
+    }
+
+    #[test]
+    fn test_id_from_href() {
+        let base = "https://api.spotify.com/v1";
+        assert_eq!(id_from_href(&format!("{base}/episodes/{ID}")), Some(ID));
+        assert_eq!(
+            id_from_href(&format!("{base}/shows/{ID}/episodes?offset=0&limit=50")),
+            Some(ID)
+        );
+        assert_eq!(
+            id_from_href(&format!("{base}/users/wizzler/playlists/{ID}/tracks")),
+            Some(ID)
+        );
+        assert_eq!(id_from_href(&format!("{base}/me/tracks")), None);
+        assert_eq!(id_from_href(&format!("{base}/tracks/")), None);
+        assert_eq!(id_from_href(""), None);
+    }
+
     #[test]
     fn test_owned() {
         // We check it twice to make sure cloning statically also works.
@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 
 /// Image object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Image {
     pub height: Option<u32>,
     pub url: String,
@@ -1,6 +1,6 @@
-//! All Spotify API endpoint response objects. Please refer to the endpoints
-//! where they are used for a link to their reference in the Spotify API
-//! documentation.
+//! All Spotify API endpoint response objects, and the request bodies sent to
+//! the mutating endpoints. Please refer to the endpoints where they are used
+//! for a link to their reference in the Spotify API documentation.
 pub mod album;
 pub mod artist;
 pub mod audio;
@@ -13,11 +13,14 @@ pub mod enums;
 pub mod error;
 pub mod idtypes;
 pub mod image;
+pub mod object;
 pub mod offset;
 pub mod page;
 pub mod playing;
 pub mod playlist;
+pub mod popularity;
 pub mod recommend;
+pub mod request;
 pub mod search;
 pub mod show;
 pub mod track;
@@ -25,23 +28,81 @@ pub mod user;
 
 pub use {
     album::*, artist::*, audio::*, auth::*, category::*, context::*, device::*, enums::*, error::*,
-    idtypes::*, image::*, offset::*, page::*, playing::*, playlist::*, recommend::*, search::*,
-    show::*, track::*, user::*,
+    idtypes::*, image::*, object::*, offset::*, page::*, playing::*, playlist::*, popularity::*,
+    recommend::*, request::*, search::*, show::*, track::*, user::*,
 };
 
+use custom_serde::null_as_default;
 use serde::{Deserialize, Serialize};
 
+use std::time::Duration;
+
 /// Followers object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
 pub struct Followers {
     // This field will always set to null, as the Web API does not support it at the moment.
     // pub href: Option<String>,
+    #[serde(default, deserialize_with = "null_as_default::deserialize")]
     pub total: u32,
 }
 
-/// A full track object or a full episode object
+/// Objects that can be followed, like artists, playlists and users. Their
+/// followers may be missing, e.g., in the responses of some endpoints.
+pub trait WithFollowers {
+    /// The followers of the object, if known.
+    fn followers(&self) -> Option<&Followers>;
+
+    /// The number of followers, or zero if it's missing.
+    #[must_use]
+    fn total_followers(&self) -> u32 {
+        self.followers().map_or(0, |followers| followers.total)
+    }
+}
+
+macro_rules! impl_with_followers {
+    ($($object:ty),+ $(,)?) => {
+        $(
+            impl WithFollowers for $object {
+                fn followers(&self) -> Option<&Followers> {
+                    self.followers.as_ref()
+                }
+            }
+        )+
+    };
+}
+
+impl_with_followers!(FullArtist, FullPlaylist, PublicUser, PrivateUser);
+
+/// Intermediate markets object, with the country codes wrapped by `Vec`
+#[derive(Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct Markets {
+    pub markets: Vec<String>,
+}
+
+/// A market where Spotify is available. The country code is kept as sent by
+/// Spotify, so that the markets that aren't in [`Country`] yet aren't lost.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AvailableMarket {
+    /// The ISO 3166-1 alpha-2 country code, e.g., `ES`.
+    pub code: String,
+    /// The market of that country, or `None` if it's not known yet.
+    pub market: Option<Market>,
+}
+
+impl From<String> for AvailableMarket {
+    fn from(code: String) -> Self {
+        let market = code.parse::<Country>().ok().map(Market::Country);
+        AvailableMarket { code, market }
+    }
+}
+
+/// A full track object or a full episode object.
+///
+/// The variant is chosen by the `type` field of the object, rather than by
+/// which of them its fields happen to match.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
-#[serde(untagged)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum PlayableItem {
     Track(track::FullTrack),
     Episode(show::FullEpisode),
@@ -61,6 +122,20 @@ impl PlayableItem {
     }
 }
 
+/// Formats the duration of a track or an episode as `m:ss`, or `h:mm:ss` if
+/// it's an hour or longer, like `3:05` or `1:02:07`. The milliseconds are
+/// truncated.
+#[must_use]
+pub fn format_duration(duration: Duration) -> String {
+    let total = duration.as_secs();
+    let (hours, minutes, seconds) = (total / 3600, total / 60 % 60, total % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -108,4 +183,15 @@ mod tests {
         assert_eq!(track_id1, &id1.uri());
         assert_eq!("spotify:track:1301WleyT98MSxVHPZCA6M", &id2.uri());
     }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::ZERO), "0:00");
+        assert_eq!(format_duration(Duration::from_millis(5999)), "0:05");
+        assert_eq!(format_duration(Duration::from_secs(185)), "3:05");
+        assert_eq!(format_duration(Duration::from_secs(3599)), "59:59");
+        assert_eq!(format_duration(Duration::from_secs(3600)), "1:00:00");
+        assert_eq!(format_duration(Duration::from_secs(3727)), "1:02:07");
+        assert_eq!(format_duration(Duration::from_secs(36000)), "10:00:00");
+    }
 }
@@ -0,0 +1,160 @@
+//! Common accessors for the objects returned by Spotify
+
+use std::collections::HashMap;
+
+use crate::{
+    Context, FullAlbum, FullArtist, FullEpisode, FullPlaylist, FullShow, FullTrack, Id,
+    PrivateUser, PublicUser, SimplifiedAlbum, SimplifiedArtist, SimplifiedEpisode,
+    SimplifiedPlaylist, SimplifiedShow, SimplifiedTrack, Type,
+};
+
+/// Objects that can be linked to in Spotify, like tracks, albums or users.
+/// This makes it possible to write code that works with any of them, e.g., to
+/// log them or to build links.
+pub trait SpotifyObject {
+    /// The type of the object.
+    fn object_type(&self) -> Type;
+
+    /// The Spotify URI of the object, e.g., `spotify:track:<id>`. This is
+    /// `None` for the objects without an ID, like local tracks.
+    fn uri(&self) -> Option<String>;
+
+    /// The link to the Web API endpoint with the full details of the object.
+    /// This is `None` if Spotify didn't send it, like for local tracks.
+    fn href(&self) -> Option<&str>;
+
+    /// The known external URLs of the object.
+    fn external_urls(&self) -> &HashMap<String, String>;
+
+    /// The URL to open the object in the Spotify web player, if known.
+    fn spotify_url(&self) -> Option<&str> {
+        self.external_urls().get("spotify").map(String::as_str)
+    }
+}
+
+macro_rules! impl_spotify_object {
+    (required: $($object:ty => $type:ident),+ $(,)?) => {
+        $(
+            impl SpotifyObject for $object {
+                fn object_type(&self) -> Type {
+                    Type::$type
+                }
+
+                fn uri(&self) -> Option<String> {
+                    Some(self.id.uri())
+                }
+
+                fn href(&self) -> Option<&str> {
+                    Some(&self.href)
+                }
+
+                fn external_urls(&self) -> &HashMap<String, String> {
+                    &self.external_urls
+                }
+            }
+        )+
+    };
+    (optional: $($object:ty => $type:ident),+ $(,)?) => {
+        $(
+            impl SpotifyObject for $object {
+                fn object_type(&self) -> Type {
+                    Type::$type
+                }
+
+                fn uri(&self) -> Option<String> {
+                    self.id.as_ref().map(Id::uri)
+                }
+
+                fn href(&self) -> Option<&str> {
+                    self.href.as_deref()
+                }
+
+                fn external_urls(&self) -> &HashMap<String, String> {
+                    &self.external_urls
+                }
+            }
+        )+
+    };
+}
+
+impl_spotify_object!(required:
+    FullAlbum => Album,
+    FullArtist => Artist,
+    FullEpisode => Episode,
+    FullPlaylist => Playlist,
+    FullShow => Show,
+    PrivateUser => User,
+    PublicUser => User,
+    SimplifiedEpisode => Episode,
+    SimplifiedPlaylist => Playlist,
+    SimplifiedShow => Show,
+);
+
+// Local tracks, and their albums and artists, don't have an ID
+impl_spotify_object!(optional:
+    FullTrack => Track,
+    SimplifiedAlbum => Album,
+    SimplifiedArtist => Artist,
+    SimplifiedTrack => Track,
+);
+
+impl SpotifyObject for Context {
+    fn object_type(&self) -> Type {
+        self._type
+    }
+
+    fn uri(&self) -> Option<String> {
+        Some(self.uri.clone())
+    }
+
+    fn href(&self) -> Option<&str> {
+        Some(&self.href)
+    }
+
+    fn external_urls(&self) -> &HashMap<String, String> {
+        &self.external_urls
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArtistId;
+
+    fn describe(object: &dyn SpotifyObject) -> String {
+        format!(
+            "{} {}",
+            object.object_type(),
+            object.uri().as_deref().unwrap_or("(local)")
+        )
+    }
+
+    #[test]
+    fn test_spotify_object() {
+        let mut artist = SimplifiedArtist {
+            external_urls: HashMap::from([(
+                "spotify".to_owned(),
+                "https://open.spotify.com/artist/0OdUWJ0sBjDrqHygGUXeCF".to_owned(),
+            )]),
+            href: Some("https://api.spotify.com/v1/artists/0OdUWJ0sBjDrqHygGUXeCF".to_owned()),
+            id: Some(ArtistId::from_id("0OdUWJ0sBjDrqHygGUXeCF").unwrap()),
+            name: "Band of Horses".to_owned(),
+        };
+        assert_eq!(
+            describe(&artist),
+            "artist spotify:artist:0OdUWJ0sBjDrqHygGUXeCF"
+        );
+        assert_eq!(
+            artist.spotify_url(),
+            Some("https://open.spotify.com/artist/0OdUWJ0sBjDrqHygGUXeCF")
+        );
+
+        // A local artist
+        artist.id = None;
+        artist.href = None;
+        artist.external_urls.clear();
+        assert_eq!(describe(&artist), "artist (local)");
+        assert_eq!(artist.href(), None);
+        assert_eq!(artist.spotify_url(), None);
+    }
+}
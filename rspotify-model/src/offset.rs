@@ -1,7 +1,11 @@
 //! Offset object
 
-/// Offset object
-#[derive(Clone, Debug, PartialEq, Eq)]
+use serde::Serialize;
+
+/// Offset object. It's serialized as `{"position": 5}` or `{"uri": "..."}`,
+/// which is the format expected by the playback endpoints.
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
 pub enum Offset {
     Position(u32),
     Uri(String),
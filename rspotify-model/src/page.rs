@@ -1,9 +1,12 @@
 //! All kinds of page object
 
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
+
+use crate::{ModelError, ModelResult};
 
 /// Paging object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Page<T> {
     pub href: String,
     pub items: Vec<T>,
@@ -14,8 +17,104 @@ pub struct Page<T> {
     pub total: u32,
 }
 
+impl<T> Page<T> {
+    /// Appends the items of the page that follows this one, so that both can
+    /// be treated as a single page. The resulting page keeps this page's
+    /// `href`, `limit`, `offset` and `previous`, while `next` and `total` are
+    /// taken from `other`, since they're the most recent. `limit` is still
+    /// the page size that was requested, so use `items.len()` to count the
+    /// merged items.
+    ///
+    /// `other` must start right after the last item of this page; otherwise,
+    /// [`ModelError::InvalidOffset`] is returned and this page is left as is.
+    pub fn extend(&mut self, other: Page<T>) -> ModelResult<()> {
+        let expected = self.offset + self.items.len() as u32;
+        if other.offset != expected {
+            return Err(ModelError::InvalidOffset(format!(
+                "the page to be merged starts at {}, but {expected} was expected",
+                other.offset
+            )));
+        }
+
+        self.items.extend(other.items);
+        self.next = other.next;
+        self.total = other.total;
+
+        Ok(())
+    }
+}
+
+/// A [`Page`] that skips the items that fail to deserialize, instead of
+/// failing as a whole. This is useful for large imports, where a single
+/// unexpected item shouldn't discard the rest of the page.
+///
+/// It can be deserialized from any response with a page, such as the ones
+/// returned by `BaseClient::request_raw`:
+///
+/// ```
+/// use rspotify_model::{LenientPage, SimplifiedArtist};
+///
+/// let json = r#"{
+///     "href": "https://api.spotify.com/v1/artists",
+///     "items": [
+///         {
+///             "external_urls": {},
+///             "href": "https://api.spotify.com/v1/artists/0OdUWJ0sBjDrqHygGUXeCF",
+///             "id": "0OdUWJ0sBjDrqHygGUXeCF",
+///             "name": "Band of Horses"
+///         },
+///         {"name": 42}
+///     ],
+///     "limit": 2,
+///     "next": null,
+///     "offset": 0,
+///     "previous": null,
+///     "total": 2
+/// }"#;
+/// let lenient: LenientPage<SimplifiedArtist> = serde_json::from_str(json).unwrap();
+/// assert_eq!(lenient.page.items.len(), 1);
+/// assert_eq!(lenient.dropped, 1);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct LenientPage<T> {
+    /// The page with the items that were deserialized successfully
+    pub page: Page<T>,
+    /// The number of items that were skipped because they failed to
+    /// deserialize
+    pub dropped: usize,
+}
+
+impl<'de, T: DeserializeOwned> Deserialize<'de> for LenientPage<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = Page::<serde_json::Value>::deserialize(deserializer)?;
+        let received = raw.items.len();
+        let items = raw
+            .items
+            .into_iter()
+            .filter_map(|item| serde_json::from_value(item).ok())
+            .collect::<Vec<T>>();
+
+        Ok(LenientPage {
+            dropped: received - items.len(),
+            page: Page {
+                href: raw.href,
+                items,
+                limit: raw.limit,
+                next: raw.next,
+                offset: raw.offset,
+                previous: raw.previous,
+                total: raw.total,
+            },
+        })
+    }
+}
+
 /// Cursor-based paging object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct CursorBasedPage<T> {
     pub href: String,
     pub items: Vec<T>,
@@ -29,6 +128,7 @@ pub struct CursorBasedPage<T> {
 
 /// Cursor object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Cursor {
     pub after: Option<String>,
 }
@@ -7,8 +7,18 @@ use crate::{Context, FullTrack};
 
 /// Playing history object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct PlayHistory {
     pub track: FullTrack,
     pub played_at: DateTime<Utc>,
     pub context: Option<Context>,
 }
+
+impl PlayHistory {
+    /// The URI of the album, playlist or artist the track was played from, if
+    /// any. There's no context when the track was played on its own.
+    #[must_use]
+    pub fn context_uri(&self) -> Option<&str> {
+        self.context.as_ref().map(|context| context.uri.as_str())
+    }
+}
@@ -5,16 +5,18 @@ use serde::{Deserialize, Serialize};
 
 use std::collections::HashMap;
 
-use crate::{Followers, Image, Page, PlayableItem, PlaylistId, PublicUser};
+use crate::{Followers, Image, Page, PlayableId, PlayableItem, PlaylistId, PublicUser};
 
 /// Playlist result object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct PlaylistResult {
     pub snapshot_id: String,
 }
 
 /// Playlist Track Reference Object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct PlaylistTracksRef {
     pub href: String,
     pub total: u32,
@@ -41,7 +43,7 @@ pub struct FullPlaylist {
     pub collaborative: bool,
     pub description: Option<String>,
     pub external_urls: HashMap<String, String>,
-    pub followers: Followers,
+    pub followers: Option<Followers>,
     pub href: String,
     pub id: PlaylistId<'static>,
     pub images: Vec<Image>,
@@ -61,8 +63,22 @@ pub struct PlaylistItem {
     pub track: Option<PlayableItem>,
 }
 
+impl PlaylistItem {
+    /// The Spotify ID of the track or episode, or `None` for local files (and
+    /// unavailable items), which have no valid ID. This makes it easy to skip
+    /// them when collecting the IDs of a playlist for other requests.
+    #[must_use]
+    pub fn spotify_id(&self) -> Option<PlayableId<'_>> {
+        if self.is_local {
+            return None;
+        }
+        self.track.as_ref()?.id()
+    }
+}
+
 /// Featured playlists object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct FeaturedPlaylists {
     pub message: String,
     pub playlists: Page<SimplifiedPlaylist>,
@@ -70,6 +86,7 @@ pub struct FeaturedPlaylists {
 
 /// Intermediate category playlists object wrapped by `Page`
 #[derive(Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct CategoryPlaylists {
     pub playlists: Page<SimplifiedPlaylist>,
 }
@@ -0,0 +1,89 @@
+//! Helpers to sort and rank objects by their popularity
+
+use std::cmp::Reverse;
+
+use crate::{FullAlbum, FullArtist, FullTrack};
+
+/// Objects with a popularity score, between 0 and 100 (the most popular).
+pub trait HasPopularity {
+    fn popularity(&self) -> u32;
+}
+
+impl HasPopularity for FullAlbum {
+    fn popularity(&self) -> u32 {
+        self.popularity
+    }
+}
+
+impl HasPopularity for FullArtist {
+    fn popularity(&self) -> u32 {
+        self.popularity
+    }
+}
+
+impl HasPopularity for FullTrack {
+    fn popularity(&self) -> u32 {
+        self.popularity
+    }
+}
+
+/// Extension methods for lists of objects with a popularity score, such as
+/// `Vec<FullTrack>` or the items of a [`Page`](crate::Page).
+pub trait PopularityExt<T: HasPopularity> {
+    /// Sorts the list from the most to the least popular. The sort is stable,
+    /// so items with the same popularity keep their relative order.
+    fn sort_by_popularity_desc(&mut self);
+
+    /// Returns the most popular item, or `None` if the list is empty. In case
+    /// of a tie, the first one is returned.
+    fn most_popular(&self) -> Option<&T>;
+}
+
+impl<T: HasPopularity> PopularityExt<T> for [T] {
+    fn sort_by_popularity_desc(&mut self) {
+        self.sort_by_key(|item| Reverse(item.popularity()));
+    }
+
+    fn most_popular(&self) -> Option<&T> {
+        self.iter().reduce(|most, item| {
+            if item.popularity() > most.popularity() {
+                item
+            } else {
+                most
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Item(&'static str, u32);
+
+    impl HasPopularity for Item {
+        fn popularity(&self) -> u32 {
+            self.1
+        }
+    }
+
+    #[test]
+    fn test_sort_by_popularity_desc() {
+        let mut items = [Item("a", 10), Item("b", 80), Item("c", 10), Item("d", 95)];
+        items.sort_by_popularity_desc();
+        assert_eq!(
+            items,
+            [Item("d", 95), Item("b", 80), Item("a", 10), Item("c", 10)]
+        );
+    }
+
+    #[test]
+    fn test_most_popular() {
+        let items = [Item("a", 10), Item("b", 80), Item("c", 80)];
+        assert_eq!(items.most_popular(), Some(&Item("b", 80)));
+
+        let empty: [Item; 0] = [];
+        assert_eq!(empty.most_popular(), None);
+    }
+}
@@ -3,17 +3,185 @@
 use serde::{Deserialize, Serialize};
 use strum::IntoStaticStr;
 
-use crate::{RecommendationsSeedType, SimplifiedTrack};
+use crate::{ModelError, ModelResult, RecommendationsSeedType, SimplifiedTrack};
 
 /// Recommendations object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Recommendations {
     pub seeds: Vec<RecommendationsSeed>,
     pub tracks: Vec<SimplifiedTrack>,
 }
 
+/// Intermediate genre seeds wrapped by `Vec`
+#[derive(Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct GenreSeeds {
+    pub genres: Vec<String>,
+}
+
+/// A snapshot of the genres accepted as recommendation seeds. Spotify may
+/// change the list at any time, so the up-to-date one should be fetched with
+/// `available_genre_seeds` when possible.
+pub const GENRE_SEEDS: &[&str] = &[
+    "acoustic",
+    "afrobeat",
+    "alt-rock",
+    "alternative",
+    "ambient",
+    "anime",
+    "black-metal",
+    "bluegrass",
+    "blues",
+    "bossanova",
+    "brazil",
+    "breakbeat",
+    "british",
+    "cantopop",
+    "chicago-house",
+    "children",
+    "chill",
+    "classical",
+    "club",
+    "comedy",
+    "country",
+    "dance",
+    "dancehall",
+    "death-metal",
+    "deep-house",
+    "detroit-techno",
+    "disco",
+    "disney",
+    "drum-and-bass",
+    "dub",
+    "dubstep",
+    "edm",
+    "electro",
+    "electronic",
+    "emo",
+    "folk",
+    "forro",
+    "french",
+    "funk",
+    "garage",
+    "german",
+    "gospel",
+    "goth",
+    "grindcore",
+    "groove",
+    "grunge",
+    "guitar",
+    "happy",
+    "hard-rock",
+    "hardcore",
+    "hardstyle",
+    "heavy-metal",
+    "hip-hop",
+    "holidays",
+    "honky-tonk",
+    "house",
+    "idm",
+    "indian",
+    "indie",
+    "indie-pop",
+    "industrial",
+    "iranian",
+    "j-dance",
+    "j-idol",
+    "j-pop",
+    "j-rock",
+    "jazz",
+    "k-pop",
+    "kids",
+    "latin",
+    "latino",
+    "malay",
+    "mandopop",
+    "metal",
+    "metal-misc",
+    "metalcore",
+    "minimal-techno",
+    "movies",
+    "mpb",
+    "new-age",
+    "new-release",
+    "opera",
+    "pagode",
+    "party",
+    "philippines-opm",
+    "piano",
+    "pop",
+    "pop-film",
+    "post-dubstep",
+    "power-pop",
+    "progressive-house",
+    "psych-rock",
+    "punk",
+    "punk-rock",
+    "r-n-b",
+    "rainy-day",
+    "reggae",
+    "reggaeton",
+    "road-trip",
+    "rock",
+    "rock-n-roll",
+    "rockabilly",
+    "romance",
+    "sad",
+    "salsa",
+    "samba",
+    "sertanejo",
+    "show-tunes",
+    "singer-songwriter",
+    "ska",
+    "sleep",
+    "songwriter",
+    "soul",
+    "soundtracks",
+    "spanish",
+    "study",
+    "summer",
+    "swedish",
+    "synth-pop",
+    "tango",
+    "techno",
+    "trance",
+    "trip-hop",
+    "turkish",
+    "work-out",
+    "world-music",
+];
+
+/// Checks that the genres are valid seeds before requesting recommendations,
+/// which would otherwise fail entirely because of a single invalid one.
+///
+/// Parameters:
+/// - seed_genres - the genre names to check
+/// - available - the list obtained with `available_genre_seeds`. If `None`,
+///   the [`GENRE_SEEDS`] snapshot is used instead, which may be outdated.
+///
+/// It returns [`ModelError::InvalidGenreSeed`] with the first invalid genre
+/// found.
+pub fn validate_genre_seeds<'a>(
+    seed_genres: impl IntoIterator<Item = &'a str>,
+    available: Option<&[String]>,
+) -> ModelResult<()> {
+    for genre in seed_genres {
+        let is_valid = match available {
+            Some(available) => available.iter().any(|x| x == genre),
+            None => GENRE_SEEDS.contains(&genre),
+        };
+        if !is_valid {
+            return Err(ModelError::InvalidGenreSeed(genre.to_owned()));
+        }
+    }
+
+    Ok(())
+}
+
 /// Recommendations seed object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct RecommendationsSeed {
     #[serde(rename = "afterFilteringSize")]
     pub after_filtering_size: u32,
@@ -0,0 +1,69 @@
+//! Request bodies sent to the mutating endpoints
+
+use serde::Serialize;
+
+use std::time::Duration;
+
+use crate::{custom_serde::option_duration_ms, ModelError, ModelResult, Offset};
+
+/// Body of the request to change a playlist's details. Only the fields that
+/// are set are sent, so that the rest are left unchanged.
+#[derive(Clone, Debug, Serialize, PartialEq, Eq, Default)]
+pub struct ChangeDetailsBody<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collaborative: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<&'a str>,
+}
+
+/// Body of the request to reorder the items in a playlist.
+#[derive(Clone, Debug, Serialize, PartialEq, Eq, Default)]
+pub struct ReorderBody<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range_start: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub insert_before: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range_length: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snapshot_id: Option<&'a str>,
+}
+
+/// Body of the request to start or resume playback, either of a context or
+/// of a list of items.
+#[derive(Clone, Debug, Serialize, PartialEq, Eq, Default)]
+pub struct StartPlaybackBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context_uri: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uris: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<Offset>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "option_duration_ms::serialize"
+    )]
+    pub position_ms: Option<Duration>,
+}
+
+impl StartPlaybackBody {
+    /// Checks that the offset is one of the items to be played when it's
+    /// given as an URI, since Spotify's error isn't very descriptive in that
+    /// case. When playing a context, its items aren't known beforehand, so
+    /// nothing is checked.
+    pub fn validate(&self) -> ModelResult<()> {
+        if let (Some(Offset::Uri(uri)), Some(uris)) = (&self.offset, &self.uris) {
+            if !uris.contains(uri) {
+                return Err(ModelError::InvalidOffset(format!(
+                    "`{uri}` is not one of the URIs to be played"
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -9,12 +9,14 @@ use crate::{
 
 /// Search for playlists
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct SearchPlaylists {
     pub playlists: Page<SimplifiedPlaylist>,
 }
 
 /// Search for albums
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct SearchAlbums {
     pub albums: Page<SimplifiedAlbum>,
 }
@@ -22,24 +24,28 @@ pub struct SearchAlbums {
 /// Search for artists
 ///
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct SearchArtists {
     pub artists: Page<FullArtist>,
 }
 
 /// Search item
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct SearchTracks {
     pub tracks: Page<FullTrack>,
 }
 
 /// Search for shows
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct SearchShows {
     pub shows: Page<SimplifiedShow>,
 }
 
 /// Search for episodes
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct SearchEpisodes {
     pub episodes: Page<SimplifiedEpisode>,
 }
@@ -60,3 +66,207 @@ pub enum SearchResult {
     #[serde(rename = "episodes")]
     Episodes(Page<SimplifiedEpisode>),
 }
+
+/// Search result of several kinds at once, as returned when searching for
+/// multiple [`SearchType`](crate::SearchType)s. Only the pages of the
+/// requested types are present.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct SearchMultipleResult {
+    pub playlists: Option<Page<SimplifiedPlaylist>>,
+    pub albums: Option<Page<SimplifiedAlbum>>,
+    pub artists: Option<Page<FullArtist>>,
+    pub tracks: Option<Page<FullTrack>>,
+    pub shows: Option<Page<SimplifiedShow>>,
+    pub episodes: Option<Page<SimplifiedEpisode>>,
+}
+
+/// Finds the track in the search results that best matches the given name
+/// and, optionally, artist, both compared case-insensitively.
+///
+/// Only the tracks whose name contains `name` (and with an artist whose name
+/// contains `artist`, if given) are considered. They are ranked by:
+///
+/// 1. An exact name over one that only contains `name`.
+/// 2. An artist with the exact name over one that only contains `artist`.
+/// 3. The highest popularity.
+///
+/// If there's still a tie, the first track in the results is returned.
+#[must_use]
+pub fn best_match<'a>(
+    results: &'a Page<FullTrack>,
+    name: &str,
+    artist: Option<&str>,
+) -> Option<&'a FullTrack> {
+    let name = name.to_lowercase();
+    let artist = artist.map(str::to_lowercase);
+
+    let mut best: Option<(&FullTrack, (u8, u8, u32))> = None;
+    for track in &results.items {
+        let artists = track.artists.iter().map(|a| a.name.as_str());
+        let score = match match_score(&track.name, artists, &name, artist.as_deref()) {
+            Some((name_score, artist_score)) => (name_score, artist_score, track.popularity),
+            None => continue,
+        };
+        let is_better = match best {
+            Some((_, best_score)) => score > best_score,
+            None => true,
+        };
+        if is_better {
+            best = Some((track, score));
+        }
+    }
+
+    best.map(|(track, _)| track)
+}
+
+/// Scores how well a track matches the lowercase query, with 2 for an exact
+/// match and 1 for a partial one. Returns `None` if it doesn't match at all.
+fn match_score<'a>(
+    track_name: &str,
+    track_artists: impl Iterator<Item = &'a str>,
+    name: &str,
+    artist: Option<&str>,
+) -> Option<(u8, u8)> {
+    fn score(value: &str, query: &str) -> u8 {
+        let value = value.to_lowercase();
+        if value == query {
+            2
+        } else if value.contains(query) {
+            1
+        } else {
+            0
+        }
+    }
+
+    let name_score = score(track_name, name);
+    let artist_score = match artist {
+        Some(artist) => track_artists.map(|a| score(a, artist)).max().unwrap_or(0),
+        None => 0,
+    };
+
+    if name_score == 0 || (artist.is_some() && artist_score == 0) {
+        None
+    } else {
+        Some((name_score, artist_score))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SimplifiedAlbum, SimplifiedArtist};
+
+    fn track(name: &str, artist: &str, popularity: u32) -> FullTrack {
+        FullTrack {
+            album: SimplifiedAlbum::default(),
+            artists: vec![SimplifiedArtist {
+                name: artist.to_owned(),
+                ..Default::default()
+            }],
+            available_markets: Vec::new(),
+            disc_number: 1,
+            duration: Default::default(),
+            explicit: false,
+            external_ids: Default::default(),
+            external_urls: Default::default(),
+            href: None,
+            id: None,
+            is_local: false,
+            is_playable: None,
+            linked_from: None,
+            restrictions: None,
+            name: name.to_owned(),
+            popularity,
+            preview_url: None,
+            track_number: 1,
+        }
+    }
+
+    fn page(items: Vec<FullTrack>) -> Page<FullTrack> {
+        Page {
+            href: String::new(),
+            limit: items.len() as u32,
+            total: items.len() as u32,
+            items,
+            next: None,
+            offset: 0,
+            previous: None,
+        }
+    }
+
+    #[test]
+    fn test_best_match_ranking() {
+        let results = page(vec![
+            track("Get Lucky (Radio Edit)", "Daft Punk", 90),
+            track("Get Lucky", "Daft Punk Tribute Band", 40),
+            track("Get Lucky", "Daft Punk", 80),
+            track("One More Time", "Daft Punk", 100),
+        ]);
+
+        // The exact name wins over a more popular partial match
+        let best = best_match(&results, "GET LUCKY", None).unwrap();
+        assert_eq!(best.popularity, 80);
+
+        // The exact artist wins over a partial one
+        let best = best_match(&results, "get lucky", Some("daft punk")).unwrap();
+        assert_eq!(best.artists[0].name, "Daft Punk");
+        assert_eq!(best.name, "Get Lucky");
+
+        // A partial name is still a match if it's the only one
+        let best = best_match(&results, "radio edit", None).unwrap();
+        assert_eq!(best.name, "Get Lucky (Radio Edit)");
+
+        assert_eq!(best_match(&results, "get lucky", Some("justice")), None);
+    }
+
+    #[test]
+    fn test_best_match_popularity_tiebreak() {
+        let results = page(vec![
+            track("Get Lucky", "Daft Punk", 60),
+            track("Get Lucky", "Daft Punk", 85),
+            track("Get Lucky", "Daft Punk", 85),
+        ]);
+
+        // The most popular one wins, and the first one if they're still tied
+        let best = best_match(&results, "get lucky", Some("daft punk")).unwrap();
+        assert!(std::ptr::eq(best, &results.items[1]));
+    }
+
+    #[test]
+    fn test_best_match_empty() {
+        let results = page(Vec::new());
+        assert_eq!(best_match(&results, "get lucky", None), None);
+        assert_eq!(best_match(&results, "get lucky", Some("daft punk")), None);
+    }
+
+    #[test]
+    fn test_match_score() {
+        let artists = || ["Daft Punk", "Pharrell Williams"].iter().copied();
+
+        assert_eq!(
+            match_score("Get Lucky", artists(), "get lucky", None),
+            Some((2, 0))
+        );
+        assert_eq!(
+            match_score("Get Lucky (Radio Edit)", artists(), "get lucky", None),
+            Some((1, 0))
+        );
+        assert_eq!(
+            match_score("Get Lucky", artists(), "get lucky", Some("daft punk")),
+            Some((2, 2))
+        );
+        assert_eq!(
+            match_score("Get Lucky", artists(), "get lucky", Some("pharrell")),
+            Some((2, 1))
+        );
+        assert_eq!(
+            match_score("Get Lucky", artists(), "get lucky", Some("justice")),
+            None
+        );
+        assert_eq!(
+            match_score("Lose Yourself to Dance", artists(), "get lucky", None),
+            None
+        );
+    }
+}
@@ -4,11 +4,13 @@ use std::collections::HashMap;
 use std::time::Duration;
 
 use crate::{
-    custom_serde::duration_ms, CopyrightType, DatePrecision, EpisodeId, Image, Page, ShowId,
+    custom_serde::duration_ms, CopyrightType, DatePrecision, EpisodeId, Image, Page, Restriction,
+    ShowId, WithRestrictions,
 };
 
 /// Copyright object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Copyright {
     pub text: String,
     #[serde(rename = "type")]
@@ -18,6 +20,7 @@ pub struct Copyright {
 /// Simplified show object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct SimplifiedShow {
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub available_markets: Vec<String>,
     pub copyrights: Vec<Copyright>,
     pub description: String,
@@ -35,12 +38,19 @@ pub struct SimplifiedShow {
 
 /// [`SimplifiedShow`] wrapped by [`Vec`]
 #[derive(Deserialize)]
-pub struct SeversalSimplifiedShows {
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct SeveralSimplifiedShows {
     pub shows: Vec<SimplifiedShow>,
 }
 
+/// Misspelled name of [`SeveralSimplifiedShows`], kept for backwards
+/// compatibility.
+#[deprecated(note = "`SeversalSimplifiedShows` was a typo, use `SeveralSimplifiedShows` instead")]
+pub type SeversalSimplifiedShows = SeveralSimplifiedShows;
+
 /// Saved show object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Show {
     pub added_at: String,
     pub show: SimplifiedShow,
@@ -49,6 +59,7 @@ pub struct Show {
 /// Full show object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct FullShow {
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub available_markets: Vec<String>,
     pub copyrights: Vec<Copyright>,
     pub description: String,
@@ -65,6 +76,33 @@ pub struct FullShow {
     pub publisher: String,
 }
 
+impl From<FullShow> for SimplifiedShow {
+    /// Drops the episodes of the show.
+    fn from(show: FullShow) -> Self {
+        SimplifiedShow {
+            available_markets: show.available_markets,
+            copyrights: show.copyrights,
+            description: show.description,
+            explicit: show.explicit,
+            external_urls: show.external_urls,
+            href: show.href,
+            id: show.id,
+            images: show.images,
+            is_externally_hosted: show.is_externally_hosted,
+            languages: show.languages,
+            media_type: show.media_type,
+            name: show.name,
+            publisher: show.publisher,
+        }
+    }
+}
+
+/// Episodes are only marked as unplayable explicitly, so a missing
+/// `is_playable` means that it can be played.
+fn playable_by_default() -> bool {
+    true
+}
+
 /// Simplified episode object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct SimplifiedEpisode {
@@ -78,6 +116,7 @@ pub struct SimplifiedEpisode {
     pub id: EpisodeId<'static>,
     pub images: Vec<Image>,
     pub is_externally_hosted: bool,
+    #[serde(default = "playable_by_default")]
     pub is_playable: bool,
     #[deprecated(note = "This `language` field is deprecated and might be \
         removed in the future by Spotify. Please use the languages field \
@@ -87,9 +126,23 @@ pub struct SimplifiedEpisode {
     pub name: String,
     pub release_date: String,
     pub release_date_precision: DatePrecision,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restrictions: Option<Restriction>,
+    /// Only available when the token has the `user-read-playback-position`
+    /// scope.
     pub resume_point: Option<ResumePoint>,
 }
 
+impl WithRestrictions for SimplifiedEpisode {
+    fn marked_playable(&self) -> bool {
+        self.is_playable
+    }
+
+    fn restrictions(&self) -> Option<&Restriction> {
+        self.restrictions.as_ref()
+    }
+}
+
 /// Full episode object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct FullEpisode {
@@ -103,6 +156,7 @@ pub struct FullEpisode {
     pub id: EpisodeId<'static>,
     pub images: Vec<Image>,
     pub is_externally_hosted: bool,
+    #[serde(default = "playable_by_default")]
     pub is_playable: bool,
     #[deprecated(note = "This `language` field is deprecated and might be \
         removed in the future by Spotify. Please use the languages field \
@@ -112,20 +166,159 @@ pub struct FullEpisode {
     pub name: String,
     pub release_date: String,
     pub release_date_precision: DatePrecision,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restrictions: Option<Restriction>,
+    /// Only available when the token has the `user-read-playback-position`
+    /// scope.
     pub resume_point: Option<ResumePoint>,
     pub show: SimplifiedShow,
 }
 
+impl WithRestrictions for FullEpisode {
+    fn marked_playable(&self) -> bool {
+        self.is_playable
+    }
+
+    fn restrictions(&self) -> Option<&Restriction> {
+        self.restrictions.as_ref()
+    }
+}
+
+impl From<FullEpisode> for SimplifiedEpisode {
+    /// Drops the show of the episode.
+    // The deprecated `language` field still has to be copied over
+    #[allow(deprecated)]
+    fn from(episode: FullEpisode) -> Self {
+        SimplifiedEpisode {
+            audio_preview_url: episode.audio_preview_url,
+            description: episode.description,
+            duration: episode.duration,
+            explicit: episode.explicit,
+            external_urls: episode.external_urls,
+            href: episode.href,
+            id: episode.id,
+            images: episode.images,
+            is_externally_hosted: episode.is_externally_hosted,
+            is_playable: episode.is_playable,
+            language: episode.language,
+            languages: episode.languages,
+            name: episode.name,
+            release_date: episode.release_date,
+            release_date_precision: episode.release_date_precision,
+            restrictions: episode.restrictions,
+            resume_point: episode.resume_point,
+        }
+    }
+}
+
+/// Objects available in a list of languages, like shows and episodes, so
+/// that they can be read the same way.
+pub trait WithLanguages {
+    /// The languages of the object as given by Spotify, e.g., `en-US`.
+    fn languages(&self) -> &[String];
+
+    /// The primary language subtag of the first language, in lowercase,
+    /// e.g., `en` for `en-US`.
+    #[must_use]
+    fn primary_language(&self) -> Option<String> {
+        let language = self.languages().first()?.split('-').next()?;
+        Some(language.to_lowercase())
+    }
+
+    /// The languages as lowercase BCP-47 tags, e.g., `en-us`, since
+    /// Spotify's casing is inconsistent.
+    #[must_use]
+    fn languages_normalized(&self) -> Vec<String> {
+        self.languages()
+            .iter()
+            .map(|language| language.to_lowercase())
+            .collect()
+    }
+}
+
+macro_rules! impl_with_languages {
+    ($($object:ty),+ $(,)?) => {
+        $(
+            impl WithLanguages for $object {
+                fn languages(&self) -> &[String] {
+                    &self.languages
+                }
+            }
+        )+
+    };
+}
+
+impl_with_languages!(SimplifiedShow, FullShow, SimplifiedEpisode, FullEpisode);
+
 /// Intermediate episodes feature object wrapped by `Vec`
 #[derive(Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct EpisodesPayload {
     pub episodes: Vec<FullEpisode>,
 }
 
 /// Resume point object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct ResumePoint {
     pub fully_played: bool,
     #[serde(with = "duration_ms", rename = "resume_position_ms")]
     pub resume_position: Duration,
 }
+
+impl ResumePoint {
+    /// The resume point of an episode that the user hasn't started.
+    pub const NOT_STARTED: Self = Self {
+        fully_played: false,
+        resume_position: Duration::ZERO,
+    };
+
+    /// Whether the user has listened to the whole episode.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.fully_played
+    }
+}
+
+/// Episodes, which may include where the user stopped listening to them, so
+/// that it can be read the same way.
+pub trait WithResumePoint {
+    /// The resume point of the episode, only available when the token has
+    /// the `user-read-playback-position` scope.
+    fn resume_point(&self) -> Option<&ResumePoint>;
+
+    /// The resume point of the episode, or [`ResumePoint::NOT_STARTED`] if
+    /// it isn't available.
+    #[must_use]
+    fn resume_point_or_default(&self) -> &ResumePoint {
+        self.resume_point().unwrap_or(&ResumePoint::NOT_STARTED)
+    }
+
+    /// Where the user stopped listening to the episode, which is zero if they
+    /// haven't started it or the resume point isn't available.
+    #[must_use]
+    fn resume_position(&self) -> Duration {
+        self.resume_point_or_default().resume_position
+    }
+
+    /// Whether the user has listened to the whole episode, which is false if
+    /// the resume point isn't available.
+    #[must_use]
+    fn is_finished(&self) -> bool {
+        self.resume_point_or_default().is_finished()
+    }
+}
+
+macro_rules! impl_with_resume_point {
+    ($($object:ty),+ $(,)?) => {
+        $(
+            impl WithResumePoint for $object {
+                fn resume_point(&self) -> Option<&ResumePoint> {
+                    self.resume_point.as_ref()
+                }
+            }
+        )+
+    };
+}
+
+impl_with_resume_point!(SimplifiedEpisode, FullEpisode);
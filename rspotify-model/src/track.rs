@@ -6,7 +6,8 @@ use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, time::Duration};
 
 use crate::{
-    custom_serde::duration_ms, PlayableId, Restriction, SimplifiedAlbum, SimplifiedArtist, TrackId,
+    custom_serde::duration_ms, Country, Market, PlayableId, Restriction, SimplifiedAlbum,
+    SimplifiedArtist, TrackId, WithRestrictions,
 };
 
 /// Full track object
@@ -20,6 +21,7 @@ pub struct FullTrack {
     #[serde(with = "duration_ms", rename = "duration_ms")]
     pub duration: Duration,
     pub explicit: bool,
+    #[serde(default)]
     pub external_ids: HashMap<String, String>,
     pub external_urls: HashMap<String, String>,
     pub href: Option<String>,
@@ -33,11 +35,143 @@ pub struct FullTrack {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub restrictions: Option<Restriction>,
     pub name: String,
+    #[serde(default)]
     pub popularity: u32,
     pub preview_url: Option<String>,
     pub track_number: u32,
 }
 
+impl WithRestrictions for FullTrack {
+    /// `is_playable` is only present when relinking is applied, so the track
+    /// is assumed to be playable otherwise.
+    fn marked_playable(&self) -> bool {
+        self.is_playable.unwrap_or(true)
+    }
+
+    fn restrictions(&self) -> Option<&Restriction> {
+        self.restrictions.as_ref()
+    }
+}
+
+impl FullTrack {
+    /// Whether the track is available in the given market, according to
+    /// `available_markets`. This is `None` when it's unknown, i.e., for
+    /// [`Market::FromToken`], or when Spotify didn't send the available
+    /// markets (which happens when the track was requested for a market).
+    #[must_use]
+    pub fn is_available_in(&self, market: Market) -> Option<bool> {
+        is_available_in(&self.available_markets, market)
+    }
+
+    /// Whether the track is available in all of the given markets, e.g., those
+    /// of the members of a group. Markets where the availability is unknown
+    /// (see [`Self::is_available_in`]) are assumed to be available.
+    #[must_use]
+    pub fn is_available_in_all(&self, markets: &[Market]) -> bool {
+        markets
+            .iter()
+            .all(|market| self.is_available_in(*market) != Some(false))
+    }
+
+    /// The markets from `all` in which the track is known to be unavailable.
+    #[must_use]
+    pub fn unavailable_markets(&self, all: &[Market]) -> Vec<Market> {
+        all.iter()
+            .copied()
+            .filter(|market| self.is_available_in(*market) == Some(false))
+            .collect()
+    }
+
+    /// Whether both objects represent the same track, unlike `==`, which also
+    /// compares the rest of the fields (e.g., the available markets vary
+    /// depending on the market the track was requested for).
+    ///
+    /// The tracks are the same if they share an ID, including the original one
+    /// in `linked_from` when it was relinked, or otherwise if they have the
+    /// same ISRC. Local tracks have neither, so they're never the same.
+    #[must_use]
+    pub fn same_as(&self, other: &FullTrack) -> bool {
+        fn ids(track: &FullTrack) -> impl Iterator<Item = &TrackId<'static>> {
+            let linked_from = track.linked_from.iter().map(|link| &link.id);
+            track.id.iter().chain(linked_from)
+        }
+        if ids(self).any(|id| ids(other).any(|other_id| id == other_id)) {
+            return true;
+        }
+
+        match (
+            self.external_ids.get("isrc"),
+            other.external_ids.get("isrc"),
+        ) {
+            (Some(isrc), Some(other_isrc)) => isrc.eq_ignore_ascii_case(other_isrc),
+            _ => false,
+        }
+    }
+}
+
+impl From<FullTrack> for SimplifiedTrack {
+    /// Drops the album, the external IDs and the popularity of the track. The
+    /// available markets are `None` if they're missing from the full track,
+    /// e.g., when it was requested for a market.
+    fn from(track: FullTrack) -> Self {
+        let available_markets = Some(track.available_markets).filter(|markets| !markets.is_empty());
+        SimplifiedTrack {
+            artists: track.artists,
+            available_markets,
+            disc_number: track.disc_number,
+            duration: track.duration,
+            explicit: track.explicit,
+            external_urls: track.external_urls,
+            href: track.href,
+            id: track.id,
+            is_local: track.is_local,
+            is_playable: track.is_playable,
+            linked_from: track.linked_from,
+            restrictions: track.restrictions,
+            name: track.name,
+            preview_url: track.preview_url,
+            track_number: track.track_number,
+        }
+    }
+}
+
+fn is_available_in(available_markets: &[String], market: Market) -> Option<bool> {
+    match market {
+        Market::Country(country) if !available_markets.is_empty() => {
+            let code = <&str>::from(country);
+            Some(available_markets.iter().any(|available| available == code))
+        }
+        _ => None,
+    }
+}
+
+/// The markets in which all of the tracks are available, in the order of the
+/// first track's `available_markets`.
+///
+/// The tracks whose available markets are unknown (see
+/// [`FullTrack::is_available_in`]) are assumed to be available everywhere, so
+/// they don't restrict the result. If none of them are known, this returns
+/// `None`. Country codes that aren't a known [`Country`] are skipped.
+#[must_use]
+pub fn common_markets(tracks: &[FullTrack]) -> Option<Vec<Market>> {
+    let mut known = tracks
+        .iter()
+        .map(|track| &track.available_markets)
+        .filter(|markets| !markets.is_empty());
+
+    let mut common = known.next()?.iter().collect::<Vec<_>>();
+    for markets in known {
+        common.retain(|code| markets.contains(code));
+    }
+
+    let common = common
+        .into_iter()
+        .filter_map(|code| code.parse::<Country>().ok())
+        .map(Market::Country)
+        .collect();
+    Some(common)
+}
+
 /// Track link object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct TrackLink {
@@ -48,6 +182,7 @@ pub struct TrackLink {
 
 /// Intermediate full track wrapped by `Vec`
 #[derive(Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct FullTracks {
     pub tracks: Vec<FullTrack>,
 }
@@ -77,8 +212,54 @@ pub struct SimplifiedTrack {
     pub track_number: u32,
 }
 
+impl WithRestrictions for SimplifiedTrack {
+    /// `is_playable` is only present when relinking is applied, so the track
+    /// is assumed to be playable otherwise.
+    fn marked_playable(&self) -> bool {
+        self.is_playable.unwrap_or(true)
+    }
+
+    fn restrictions(&self) -> Option<&Restriction> {
+        self.restrictions.as_ref()
+    }
+}
+
+impl SimplifiedTrack {
+    /// Whether the track is available in the given market, according to
+    /// `available_markets`. This is `None` when it's unknown, i.e., for
+    /// [`Market::FromToken`], or when Spotify didn't send the available
+    /// markets (which happens when the track was requested for a market).
+    #[must_use]
+    pub fn is_available_in(&self, market: Market) -> Option<bool> {
+        is_available_in(
+            self.available_markets.as_deref().unwrap_or_default(),
+            market,
+        )
+    }
+
+    /// Whether the track is available in all of the given markets, e.g., those
+    /// of the members of a group. Markets where the availability is unknown
+    /// (see [`Self::is_available_in`]) are assumed to be available.
+    #[must_use]
+    pub fn is_available_in_all(&self, markets: &[Market]) -> bool {
+        markets
+            .iter()
+            .all(|market| self.is_available_in(*market) != Some(false))
+    }
+
+    /// The markets from `all` in which the track is known to be unavailable.
+    #[must_use]
+    pub fn unavailable_markets(&self, all: &[Market]) -> Vec<Market> {
+        all.iter()
+            .copied()
+            .filter(|market| self.is_available_in(*market) == Some(false))
+            .collect()
+    }
+}
+
 /// Saved track object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct SavedTrack {
     pub added_at: DateTime<Utc>,
     pub track: FullTrack,
@@ -33,8 +33,25 @@ pub struct PrivateUser {
     pub product: Option<SubscriptionLevel>,
 }
 
+impl PrivateUser {
+    /// Whether explicit content may be played for this user, according to
+    /// their account settings. This is true unless the explicit content filter
+    /// is enabled, e.g., to skip tracks with [`FullTrack::explicit`] set when
+    /// building a queue.
+    ///
+    /// Note that `explicit_content` is only available with the
+    /// `user-read-private` scope; otherwise, it's assumed to be allowed.
+    ///
+    /// [`FullTrack::explicit`]: crate::FullTrack::explicit
+    #[must_use]
+    pub fn explicit_allowed(&self) -> bool {
+        !matches!(&self.explicit_content, Some(settings) if settings.filter_enabled)
+    }
+}
+
 /// Explicit content setting object
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct ExplicitContent {
     pub filter_enabled: bool,
     pub filter_locked: bool,
@@ -1,14 +1,17 @@
 use crate::{
     auth_urls,
-    clients::{BaseClient, OAuthClient},
+    clients::{keep_previous_token_fields, BaseClient, OAuthClient},
     http::{Form, HttpClient},
-    join_scopes, params,
+    join_scopes,
+    model::Market,
+    params,
     sync::Mutex,
-    ClientResult, Config, Credentials, OAuth, Token,
+    util, ClientError, ClientResult, Config, Credentials, OAuth, Token,
 };
 
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use maybe_async::maybe_async;
 use url::Url;
@@ -45,6 +48,9 @@ use url::Url;
 ///    See [this related example][example-refresh-token] to learn more about
 ///    refreshing tokens.
 ///
+/// For devices without a browser or a keyboard, like TVs or consoles, see
+/// [`Self::begin_device_auth`].
+///
 /// There's a [webapp example][example-webapp] for more details on how you can
 /// implement it for something like a web server, or [this one][example-main]
 /// for a CLI use case.
@@ -67,7 +73,10 @@ pub struct AuthCodeSpotify {
     pub creds: Credentials,
     pub oauth: OAuth,
     pub config: Config,
-    pub token: Arc<Mutex<Option<Token>>>,
+    pub(in crate) token: Arc<Mutex<Option<Token>>>,
+    /// The user's market, cached along with the access token it was
+    /// requested with
+    pub(in crate) user_market: Arc<Mutex<Option<(String, Market)>>>,
     pub(in crate) http: HttpClient,
 }
 
@@ -90,14 +99,20 @@ impl BaseClient for AuthCodeSpotify {
         &self.config
     }
 
+    fn get_config_mut(&mut self) -> &mut Config {
+        &mut self.config
+    }
+
     /// Refetch the current access token given a refresh token. May return
     /// `None` if there's no access/refresh token.
     async fn refetch_token(&self) -> ClientResult<Option<Token>> {
         match self.token.lock().await.unwrap().as_ref() {
-            Some(Token {
-                refresh_token: Some(refresh_token),
-                ..
-            }) => {
+            Some(
+                previous @ Token {
+                    refresh_token: Some(refresh_token),
+                    ..
+                },
+            ) => {
                 let mut data = Form::new();
                 data.insert(params::REFRESH_TOKEN, refresh_token);
                 data.insert(params::GRANT_TYPE, params::REFRESH_TOKEN);
@@ -107,7 +122,7 @@ impl BaseClient for AuthCodeSpotify {
                     .auth_headers()
                     .expect("No client secret set in the credentials.");
                 let mut token = self.fetch_access_token(&data, Some(&headers)).await?;
-                token.refresh_token = Some(refresh_token.to_string());
+                keep_previous_token_fields(&mut token, previous, &self.oauth.scopes);
                 Ok(Some(token))
             }
             _ => Ok(None),
@@ -123,6 +138,10 @@ impl OAuthClient for AuthCodeSpotify {
         &self.oauth
     }
 
+    fn get_user_market_cache(&self) -> Arc<Mutex<Option<(String, Market)>>> {
+        Arc::clone(&self.user_market)
+    }
+
     /// Obtains a user access token given a code, as part of the OAuth
     /// authentication. The access token will be saved internally.
     async fn request_token(&self, code: &str) -> ClientResult<()> {
@@ -179,6 +198,7 @@ impl AuthCodeSpotify {
         Self {
             creds,
             oauth,
+            http: HttpClient::default().with_logging(config.log_requests),
             config,
             ..Default::default()
         }
@@ -205,4 +225,88 @@ impl AuthCodeSpotify {
         let parsed = Url::parse_with_params(auth_urls::AUTHORIZE, payload)?;
         Ok(parsed.into())
     }
+
+    /// Starts the authorization on a device with limited input, like a TV or
+    /// a console. Spotify doesn't support the device authorization grant, so
+    /// this still follows the authorization code flow: the returned handle
+    /// exposes the authorization URL, which can be shown as a QR code so that
+    /// the user logs in from another device, and then completes the flow with
+    /// the redirect URL or the code once the app receives it.
+    pub fn begin_device_auth(&self) -> ClientResult<DeviceAuthHandle<'_>> {
+        Ok(DeviceAuthHandle {
+            client: self,
+            url: self.get_authorize_url(false)?,
+        })
+    }
+}
+
+/// An authorization started with [`AuthCodeSpotify::begin_device_auth`].
+///
+/// The redirect URI is still opened on the device the user logs in with, so
+/// the app has to obtain the response somehow, e.g., from a local server
+/// capturing the redirect or from a code pasted by the user.
+#[derive(Debug)]
+pub struct DeviceAuthHandle<'a> {
+    client: &'a AuthCodeSpotify,
+    url: String,
+}
+
+impl DeviceAuthHandle<'_> {
+    /// The URL the user has to visit to log in, meant to be displayed as is
+    /// or encoded as a QR code.
+    #[must_use]
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Finishes the authorization with the response obtained by the app,
+    /// which can be either the URL the user was redirected to or just the
+    /// code in it. The state of the URL is verified as explained in
+    /// [`OAuthClient::parse_response_code`], but it can't be in the
+    /// latter case. The access token will be saved internally.
+    #[maybe_async]
+    pub async fn complete(&self, response: &str) -> ClientResult<()> {
+        let response = response.trim();
+        let code = if Url::parse(response).is_ok() {
+            self.client.parse_response_code(response)?
+        } else if !response.is_empty() && !response.contains(char::is_whitespace) {
+            response.to_owned()
+        } else {
+            return Err(ClientError::InvalidRequest(
+                "unable to parse the response code".to_owned(),
+            ));
+        };
+
+        self.client.request_token(&code).await
+    }
+
+    /// Calls `response` every `interval` until it returns the redirect URL or
+    /// the code, and then finishes the authorization with
+    /// [`Self::complete`]. `response` is expected to check without blocking
+    /// whether the app has received it yet.
+    ///
+    /// Fails with [`ClientError::AuthTimeout`] if there's still no response
+    /// after `max_attempts` calls, e.g., because the user never logged in.
+    #[maybe_async]
+    pub async fn poll<F>(
+        &self,
+        mut response: F,
+        interval: Duration,
+        max_attempts: u32,
+    ) -> ClientResult<()>
+    where
+        F: FnMut() -> Option<String>,
+    {
+        for attempt in 1..=max_attempts {
+            if let Some(response) = response() {
+                return self.complete(&response).await;
+            }
+
+            if attempt < max_attempts {
+                util::sleep(interval).await;
+            }
+        }
+
+        Err(ClientError::AuthTimeout)
+    }
 }
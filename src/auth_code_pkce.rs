@@ -1,9 +1,11 @@
 use crate::{
     alphabets, auth_urls,
-    clients::{BaseClient, OAuthClient},
+    clients::{keep_previous_token_fields, BaseClient, OAuthClient},
     generate_random_string,
     http::{Form, HttpClient},
-    join_scopes, params,
+    join_scopes,
+    model::Market,
+    params,
     sync::Mutex,
     ClientResult, Config, Credentials, OAuth, Token,
 };
@@ -35,9 +37,12 @@ pub struct AuthCodePkceSpotify {
     pub creds: Credentials,
     pub oauth: OAuth,
     pub config: Config,
-    pub token: Arc<Mutex<Option<Token>>>,
+    pub(in crate) token: Arc<Mutex<Option<Token>>>,
     /// The code verifier for the authentication process
     pub verifier: Option<String>,
+    /// The user's market, cached along with the access token it was
+    /// requested with
+    pub(in crate) user_market: Arc<Mutex<Option<(String, Market)>>>,
     pub(in crate) http: HttpClient,
 }
 
@@ -60,19 +65,25 @@ impl BaseClient for AuthCodePkceSpotify {
         &self.config
     }
 
+    fn get_config_mut(&mut self) -> &mut Config {
+        &mut self.config
+    }
+
     async fn refetch_token(&self) -> ClientResult<Option<Token>> {
         match self.token.lock().await.unwrap().as_ref() {
-            Some(Token {
-                refresh_token: Some(refresh_token),
-                ..
-            }) => {
+            Some(
+                previous @ Token {
+                    refresh_token: Some(refresh_token),
+                    ..
+                },
+            ) => {
                 let mut data = Form::new();
                 data.insert(params::GRANT_TYPE, params::GRANT_TYPE_REFRESH_TOKEN);
                 data.insert(params::REFRESH_TOKEN, refresh_token);
                 data.insert(params::CLIENT_ID, &self.creds.id);
 
                 let mut token = self.fetch_access_token(&data, None).await?;
-                token.refresh_token = Some(refresh_token.to_string());
+                keep_previous_token_fields(&mut token, previous, &self.oauth.scopes);
                 Ok(Some(token))
             }
             _ => Ok(None),
@@ -88,6 +99,10 @@ impl OAuthClient for AuthCodePkceSpotify {
         &self.oauth
     }
 
+    fn get_user_market_cache(&self) -> Arc<Mutex<Option<(String, Market)>>> {
+        Arc::clone(&self.user_market)
+    }
+
     /// Note that the code verifier must be set at this point, either manually
     /// or with [`Self::get_authorize_url`]. Otherwise, this function will
     /// panic.
@@ -144,6 +159,7 @@ impl AuthCodePkceSpotify {
         Self {
             creds,
             oauth,
+            http: HttpClient::default().with_logging(config.log_requests),
             config,
             ..Default::default()
         }
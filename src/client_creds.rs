@@ -25,7 +25,7 @@ use std::sync::Arc;
 pub struct ClientCredsSpotify {
     pub config: Config,
     pub creds: Credentials,
-    pub token: Arc<Mutex<Option<Token>>>,
+    pub(in crate) token: Arc<Mutex<Option<Token>>>,
     pub(in crate) http: HttpClient,
 }
 
@@ -48,6 +48,14 @@ impl BaseClient for ClientCredsSpotify {
         &self.config
     }
 
+    fn get_config_mut(&mut self) -> &mut Config {
+        &mut self.config
+    }
+
+    fn is_user_authorized(&self) -> bool {
+        false
+    }
+
     /// Note that refetching a token in the Client Credentials flow is
     /// equivalent to requesting a token from scratch, since there's no refresh
     /// token available.
@@ -84,6 +92,7 @@ impl ClientCredsSpotify {
     #[must_use]
     pub fn with_config(creds: Credentials, config: Config) -> Self {
         Self {
+            http: HttpClient::default().with_logging(config.log_requests),
             config,
             creds,
             ..Default::default()
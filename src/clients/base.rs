@@ -1,22 +1,29 @@
 use crate::{
     auth_urls,
     clients::{
-        convert_result,
-        pagination::{paginate, Paginator},
+        convert_result, convert_value, empty_search_result, extend_search_result,
+        pagination::{paginate, paginate_chunks, paginate_next, Paginator},
+        search_result_len, validate_chunk_size, validate_search_query,
     },
-    http::{BaseHttpClient, Form, Headers, HttpClient, Query},
+    http::{BaseHttpClient, Form, Headers, HttpClient, Method, Query},
     join_ids,
     model::*,
     sync::Mutex,
-    util::build_map,
-    ClientResult, Config, Credentials, Token,
+    util::{self, build_map},
+    BulkResult, ClientError, ClientResult, Config, Credentials, Token,
 };
 
-use std::{collections::HashMap, fmt, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    sync::Arc,
+};
 
 use chrono::Utc;
 use maybe_async::maybe_async;
-use serde_json::Value;
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use url::Url;
 
 /// This trait implements the basic endpoints from the Spotify API that may be
 /// accessed without user authorization, including parts of the authentication
@@ -27,6 +34,7 @@ where
     Self: Send + Sync + Default + Clone + fmt::Debug,
 {
     fn get_config(&self) -> &Config;
+    fn get_config_mut(&mut self) -> &mut Config;
     fn get_http(&self) -> &HttpClient;
     fn get_creds(&self) -> &Credentials;
 
@@ -35,6 +43,45 @@ where
     /// be mutable (the token is accessed to from every endpoint).
     fn get_token(&self) -> Arc<Mutex<Option<Token>>>;
 
+    /// Returns a copy of the current token, if any, e.g., to persist it.
+    async fn token(&self) -> Option<Token> {
+        self.get_token().lock().await.unwrap().clone()
+    }
+
+    /// Replaces the current token, e.g., with one restored from storage
+    /// managed by the application. Note that it's not written to the cache
+    /// file; see [`Config::token_cached`] for that.
+    async fn set_token(&self, token: Token) {
+        *self.get_token().lock().await.unwrap() = Some(token);
+    }
+
+    /// Whether the requests are authorized on behalf of a user, which is
+    /// required by some parameters like [`Market::FromToken`]. This is only
+    /// false for the Client Credentials flow.
+    fn is_user_authorized(&self) -> bool {
+        true
+    }
+
+    /// Returns a copy of the client that retries failed `GET` requests up to
+    /// `max_retries` times, overriding [`Config::max_retries`] for the calls
+    /// made with it, e.g., `spotify.with_retry(5).track(id)`.
+    ///
+    /// The copy shares the token and the rate limiter with the original
+    /// client, so it's cheap to create one per call.
+    #[must_use]
+    fn with_retry(&self, max_retries: u32) -> Self {
+        let mut client = self.clone();
+        client.get_config_mut().max_retries = max_retries;
+        client
+    }
+
+    /// Returns a copy of the client that never retries failed requests, for
+    /// latency-sensitive calls. See [`Self::with_retry`].
+    #[must_use]
+    fn without_retry(&self) -> Self {
+        self.with_retry(0)
+    }
+
     /// If it's a relative URL like "me", the prefix is appended to it.
     /// Otherwise, the same URL is returned.
     fn endpoint_url(&self, url: &str) -> String {
@@ -81,7 +128,8 @@ where
         self.write_token_cache().await
     }
 
-    /// The headers required for authenticated requests to the API.
+    /// The headers required for authenticated requests to the API, along with
+    /// the configured [`Config::accept_language`].
     ///
     /// Since this is accessed by authenticated requests always, it's where the
     /// automatic reauthentication takes place, if enabled.
@@ -91,13 +139,19 @@ where
             .await
             .expect("Failed to re-authenticate automatically, please authenticate");
 
-        self.get_token()
+        let mut headers = self
+            .get_token()
             .lock()
             .await
             .expect("Failed to acquire lock")
             .as_ref()
             .expect("RSpotify not authenticated")
-            .auth_headers()
+            .auth_headers();
+
+        if let Some(language) = &self.get_config().accept_language {
+            headers.insert("accept-language".to_owned(), language.as_str().to_owned());
+        }
+        headers
     }
 
     // HTTP-related methods for the Spotify client. It wraps the basic HTTP
@@ -114,6 +168,19 @@ where
     //   requests to reduce the code needed for endpoints and make them as
     //   concise as possible.
 
+    /// Waits until the request is allowed by the configured
+    /// [`Config::rate_limiter`], if any.
+    #[doc(hidden)]
+    async fn throttle(&self) {
+        if let Some(limiter) = &self.get_config().rate_limiter {
+            let delay = limiter.reserve();
+            if !delay.is_zero() {
+                log::debug!("Rate limit reached, waiting {:?}", delay);
+                util::sleep(delay).await;
+            }
+        }
+    }
+
     #[doc(hidden)]
     #[inline]
     async fn get(
@@ -123,7 +190,35 @@ where
         payload: &Query<'_>,
     ) -> ClientResult<String> {
         let url = self.endpoint_url(url);
-        Ok(self.get_http().get(&url, headers, payload).await?)
+        let config = self.get_config();
+
+        // Only `GET` requests are retried, since they're idempotent.
+        let mut attempt = 0;
+        loop {
+            self.throttle().await;
+            match self.get_http().get(&url, headers, payload).await {
+                Err(err)
+                    if attempt < config.max_retries
+                        && matches!(err.status_code(), Some(500..=599)) =>
+                {
+                    let delay = util::retry_backoff(
+                        config.retry_backoff_base,
+                        config.retry_backoff_cap,
+                        attempt,
+                    );
+                    log::warn!(
+                        "Server error on GET {}, retrying in {:?} (attempt {}/{})",
+                        url,
+                        delay,
+                        attempt + 1,
+                        config.max_retries
+                    );
+                    util::sleep(delay).await;
+                    attempt += 1;
+                }
+                result => return Ok(result?),
+            }
+        }
     }
 
     #[doc(hidden)]
@@ -135,6 +230,7 @@ where
         payload: &Value,
     ) -> ClientResult<String> {
         let url = self.endpoint_url(url);
+        self.throttle().await;
         Ok(self.get_http().post(&url, headers, payload).await?)
     }
 
@@ -147,6 +243,7 @@ where
         payload: &Form<'_>,
     ) -> ClientResult<String> {
         let url = self.endpoint_url(url);
+        self.throttle().await;
         Ok(self.get_http().post_form(&url, headers, payload).await?)
     }
 
@@ -159,6 +256,7 @@ where
         payload: &Value,
     ) -> ClientResult<String> {
         let url = self.endpoint_url(url);
+        self.throttle().await;
         Ok(self.get_http().put(&url, headers, payload).await?)
     }
 
@@ -171,38 +269,171 @@ where
         payload: &Value,
     ) -> ClientResult<String> {
         let url = self.endpoint_url(url);
+        self.throttle().await;
         Ok(self.get_http().delete(&url, headers, payload).await?)
     }
 
     // The wrappers for the endpoints, which also includes the required
     // autentication.
 
+    /// Refreshes the token after a request was rejected with `401
+    /// Unauthorized`, if configured to do so with
+    /// [`Config::token_refresh_on_unauthorized`]. Returns whether the token
+    /// was refreshed, in which case the request should be retried once.
+    ///
+    /// If the token can't be refreshed or written to the cache, the failure
+    /// is logged and `false` is returned, so that the caller keeps the
+    /// original `401` error.
+    #[doc(hidden)]
+    async fn refresh_on_unauthorized(&self, result: &ClientResult<String>) -> bool {
+        let unauthorized = matches!(
+            result,
+            Err(ClientError::Http(err)) if err.status_code() == Some(401)
+        );
+        if !unauthorized || !self.get_config().token_refresh_on_unauthorized {
+            return false;
+        }
+
+        log::info!("Request was unauthorized, refreshing the token");
+        let token = match self.refetch_token().await {
+            Ok(Some(token)) => token,
+            Ok(None) => return false,
+            Err(err) => {
+                log::warn!("Couldn't refresh the token: {}", err);
+                return false;
+            }
+        };
+
+        *self.get_token().lock().await.unwrap() = Some(token);
+        if let Err(err) = self.write_token_cache().await {
+            log::warn!("Couldn't write the refreshed token to the cache: {}", err);
+            return false;
+        }
+
+        true
+    }
+
     #[doc(hidden)]
     #[inline]
     async fn endpoint_get(&self, url: &str, payload: &Query<'_>) -> ClientResult<String> {
         let headers = self.auth_headers().await;
-        self.get(url, Some(&headers), payload).await
+        let result = self.get(url, Some(&headers), payload).await;
+        if self.refresh_on_unauthorized(&result).await {
+            let headers = self.auth_headers().await;
+            return self.get(url, Some(&headers), payload).await;
+        }
+        result
     }
 
     #[doc(hidden)]
     #[inline]
     async fn endpoint_post(&self, url: &str, payload: &Value) -> ClientResult<String> {
         let headers = self.auth_headers().await;
-        self.post(url, Some(&headers), payload).await
+        let result = self.post(url, Some(&headers), payload).await;
+        if self.refresh_on_unauthorized(&result).await {
+            let headers = self.auth_headers().await;
+            return self.post(url, Some(&headers), payload).await;
+        }
+        result
     }
 
     #[doc(hidden)]
     #[inline]
     async fn endpoint_put(&self, url: &str, payload: &Value) -> ClientResult<String> {
         let headers = self.auth_headers().await;
-        self.put(url, Some(&headers), payload).await
+        let result = self.put(url, Some(&headers), payload).await;
+        if self.refresh_on_unauthorized(&result).await {
+            let headers = self.auth_headers().await;
+            return self.put(url, Some(&headers), payload).await;
+        }
+        result
     }
 
     #[doc(hidden)]
     #[inline]
     async fn endpoint_delete(&self, url: &str, payload: &Value) -> ClientResult<String> {
         let headers = self.auth_headers().await;
-        self.delete(url, Some(&headers), payload).await
+        let result = self.delete(url, Some(&headers), payload).await;
+        if self.refresh_on_unauthorized(&result).await {
+            let headers = self.auth_headers().await;
+            return self.delete(url, Some(&headers), payload).await;
+        }
+        result
+    }
+
+    /// Sends a request to any endpoint of the Spotify API, with the
+    /// authentication headers of the client. This is a low-level escape hatch
+    /// for endpoints that aren't available as methods yet; the response is
+    /// returned as raw JSON so that it can be deserialized by the caller, or
+    /// `null` if it's empty.
+    ///
+    /// Parameters:
+    /// - method - the HTTP method of the endpoint
+    /// - path - the URL relative to [`Config::prefix`], like `me/player`, or
+    ///   an absolute URL
+    /// - query - the query parameters
+    /// - body - the JSON body, which is ignored for `GET` requests. By default
+    ///   an empty object is sent.
+    async fn request_raw(
+        &self,
+        method: Method,
+        path: &str,
+        query: &[(&str, &str)],
+        body: Option<Value>,
+    ) -> ClientResult<Value> {
+        // The query parameters are appended to the URL directly, since the HTTP
+        // client only supports them for `GET` requests.
+        let url = Url::parse_with_params(&self.endpoint_url(path), query)?;
+        let body = body.unwrap_or_else(|| json!({}));
+        let result = match method {
+            Method::Get => self.endpoint_get(url.as_str(), &Query::new()).await?,
+            Method::Post => self.endpoint_post(url.as_str(), &body).await?,
+            Method::Put => self.endpoint_put(url.as_str(), &body).await?,
+            Method::Delete => self.endpoint_delete(url.as_str(), &body).await?,
+        };
+
+        if result.is_empty() {
+            Ok(Value::Null)
+        } else {
+            convert_result(&result)
+        }
+    }
+
+    /// Iterates over the items of the given page and the ones after it, which
+    /// are requested by following the `next` URL of each page. This is useful
+    /// to continue a page returned by a manually paginated method or by
+    /// [`Self::request_raw`], for any paginated endpoint.
+    ///
+    /// The pages are requested lazily, i.e., only once the items of the
+    /// previous one have been consumed. Like every other request, they're
+    /// subject to the configured [`Config::rate_limiter`] and
+    /// [`Config::max_retries`].
+    fn page_stream<'a, T>(&'a self, first: Page<T>) -> Paginator<'a, ClientResult<T>>
+    where
+        T: DeserializeOwned + Send + Unpin + 'a,
+    {
+        paginate_next(first, move |url| self.next_page(url))
+    }
+
+    /// Requests the page at the given `next` URL of a previous page. See
+    /// [`Self::page_stream`] to automatically request all of them.
+    async fn next_page<T: DeserializeOwned>(&self, url: String) -> ClientResult<Page<T>> {
+        let result = self.endpoint_get(&url, &Query::new()).await?;
+        convert_result(&result)
+    }
+
+    /// Downloads the 30 second MP3 preview of a track or an episode, given its
+    /// `preview_url` or `audio_preview_url`, which may be passed as is. Fails
+    /// with [`ClientError::MissingPreview`] if it's `None`, which is common
+    /// in some markets.
+    ///
+    /// The previews are hosted outside of the Spotify API, so the request is
+    /// sent without the authentication headers and isn't subject to
+    /// [`Config::rate_limiter`] or [`Config::max_retries`]. It does reuse the
+    /// client's HTTP connections, though.
+    async fn download_preview(&self, preview_url: Option<&str>) -> ClientResult<Vec<u8>> {
+        let url = preview_url.ok_or(ClientError::MissingPreview)?;
+        Ok(self.get_http().get_bytes(url, None).await?)
     }
 
     /// Updates the cache file at the internal cache path.
@@ -244,6 +475,13 @@ where
     ///
     /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-track)
     async fn track(&self, track_id: TrackId<'_>) -> ClientResult<FullTrack> {
+        let value = self.track_value(track_id).await?;
+        convert_value(value)
+    }
+
+    /// The same as [`Self::track`], but returning the raw JSON response, e.g.,
+    /// to read fields that aren't available in the model yet.
+    async fn track_value(&self, track_id: TrackId<'_>) -> ClientResult<Value> {
         let url = format!("tracks/{}", track_id.id());
         let result = self.endpoint_get(&url, &Query::new()).await?;
         convert_result(&result)
@@ -269,6 +507,33 @@ where
         convert_result::<FullTracks>(&result).map(|x| x.tracks)
     }
 
+    /// Streams any number of tracks, with as many requests to [`Self::tracks`]
+    /// as needed, so that they can be processed as soon as each chunk is
+    /// received. Each track comes with its index in `track_ids`. A chunk that
+    /// fails yields its error, and the following chunks are still requested.
+    ///
+    /// Up to [`Config::stream_concurrency`] chunks are requested at the same
+    /// time in async mode. An invalid `chunk_size` is yielded as the only
+    /// error.
+    ///
+    /// Parameters:
+    /// - track_ids - a list of spotify URIs, URLs or IDs
+    /// - market - an ISO 3166-1 alpha-2 country code or the string from_token.
+    /// - chunk_size - the number of tracks per request, up to the 50 allowed
+    ///   by Spotify, which is the default
+    fn tracks_stream<'a>(
+        &'a self,
+        track_ids: impl IntoIterator<Item = TrackId<'a>> + Send + 'a,
+        market: Option<Market>,
+        chunk_size: Option<usize>,
+    ) -> Paginator<'a, ClientResult<(usize, FullTrack)>> {
+        let track_ids = track_ids.into_iter().collect::<Vec<_>>();
+        let concurrency = self.get_config().stream_concurrency;
+        paginate_chunks(track_ids.len(), chunk_size, 50, concurrency, move |range| {
+            self.tracks(track_ids[range].to_vec(), market)
+        })
+    }
+
     /// Returns a single artist given the artist's ID, URI or URL.
     ///
     /// Parameters:
@@ -276,6 +541,12 @@ where
     ///
     /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-an-artist)
     async fn artist(&self, artist_id: ArtistId<'_>) -> ClientResult<FullArtist> {
+        let value = self.artist_value(artist_id).await?;
+        convert_value(value)
+    }
+
+    /// The same as [`Self::artist`], but returning the raw JSON response.
+    async fn artist_value(&self, artist_id: ArtistId<'_>) -> ClientResult<Value> {
         let url = format!("artists/{}", artist_id.id());
         let result = self.endpoint_get(&url, &Query::new()).await?;
         convert_result(&result)
@@ -387,14 +658,33 @@ where
 
     /// Returns a single album given the album's ID, URIs or URL.
     ///
+    /// Its `tracks` only include the first page of the album's tracks. Use
+    /// [`Self::album_track`] to iterate over all of them.
+    ///
     /// Parameters:
     /// - album_id - the album ID, URI or URL
+    /// - market - an ISO 3166-1 alpha-2 country code or the string from_token.
     ///
     /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-an-album)
-    async fn album(&self, album_id: AlbumId<'_>) -> ClientResult<FullAlbum> {
-        let url = format!("albums/{}", album_id.id());
+    async fn album(
+        &self,
+        album_id: AlbumId<'_>,
+        market: Option<Market>,
+    ) -> ClientResult<FullAlbum> {
+        let value = self.album_value(album_id, market).await?;
+        convert_value(value)
+    }
 
-        let result = self.endpoint_get(&url, &Query::new()).await?;
+    /// The same as [`Self::album`], but returning the raw JSON response.
+    async fn album_value(
+        &self,
+        album_id: AlbumId<'_>,
+        market: Option<Market>,
+    ) -> ClientResult<Value> {
+        let params = build_map([("market", market.map(Into::into))]);
+
+        let url = format!("albums/{}", album_id.id());
+        let result = self.endpoint_get(&url, &params).await?;
         convert_result(&result)
     }
 
@@ -424,9 +714,17 @@ where
     /// - type - the type of item to return. One of 'artist', 'album', 'track',
     ///  'playlist', 'show' or 'episode'
     /// - market - An ISO 3166-1 alpha-2 country code or the string from_token.
-    /// - include_external: Optional.Possible values: audio. If
-    ///   include_external=audio is specified the response will include any
-    ///   relevant audio content that is hosted externally.  
+    ///   Without it, track relinking isn't applied, so some of the returned
+    ///   tracks may not be playable for the user.
+    /// - include_external: Optional. Possible values: audio. Passing
+    ///   `Some(IncludeExternal::Audio)` signals that the client can play
+    ///   externally hosted audio content, which is then marked as playable in
+    ///   the response. By default, it's still returned, but marked as
+    ///   unplayable. In practice, this only affects searches of shows and
+    ///   episodes.
+    ///
+    /// An empty query, or one with whitespace only, is rejected with
+    /// [`ClientError::InvalidRequest`] without sending the request.
     ///
     /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/search)
     async fn search(
@@ -438,6 +736,8 @@ where
         limit: Option<u32>,
         offset: Option<u32>,
     ) -> ClientResult<SearchResult> {
+        validate_search_query(q)?;
+
         let limit = limit.map(|s| s.to_string());
         let offset = offset.map(|s| s.to_string());
         let params = build_map([
@@ -453,6 +753,134 @@ where
         convert_result(&result)
     }
 
+    /// Search for items of several types at once, such as tracks and artists,
+    /// with a single request. The result only has the pages of the requested
+    /// types.
+    ///
+    /// Parameters:
+    /// - q - the search query
+    /// - types - the types of item to return. Repeated types are ignored, and
+    ///   at least one is required.
+    /// - market - An ISO 3166-1 alpha-2 country code or the string from_token.
+    /// - include_external: Optional. Possible values: audio.
+    /// - limit - the number of items to return for each type
+    /// - offset - the index of the first item to return for each type
+    ///
+    /// See [`Self::search`] for more information.
+    async fn search_multiple(
+        &self,
+        q: &str,
+        types: impl IntoIterator<Item = SearchType> + Send,
+        market: Option<Market>,
+        include_external: Option<IncludeExternal>,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> ClientResult<SearchMultipleResult> {
+        validate_search_query(q)?;
+
+        let mut unique_types: Vec<&'static str> = Vec::new();
+        for _type in types {
+            let _type = _type.into();
+            if !unique_types.contains(&_type) {
+                unique_types.push(_type);
+            }
+        }
+        if unique_types.is_empty() {
+            return Err(ClientError::InvalidRequest(
+                "at least one search type is required".to_owned(),
+            ));
+        }
+        let types = unique_types.join(",");
+        let limit = limit.map(|s| s.to_string());
+        let offset = offset.map(|s| s.to_string());
+        let params = build_map([
+            ("q", Some(q)),
+            ("type", Some(types.as_str())),
+            ("market", market.map(Into::into)),
+            ("include_external", include_external.map(Into::into)),
+            ("limit", limit.as_deref()),
+            ("offset", offset.as_deref()),
+        ]);
+
+        let result = self.endpoint_get("search", &params).await?;
+        convert_result(&result)
+    }
+
+    /// Search for up to `max` items and return them in a single page, without
+    /// having to paginate manually. Spotify doesn't allow `offset + limit` to
+    /// be larger than 1000, so `max` is clamped to that value. Fewer items are
+    /// returned if the search runs out of results earlier.
+    ///
+    /// The items are requested in chunks of [`Config::pagination_chunks`],
+    /// with at most 50 per request, and nothing is requested if `max` is zero.
+    ///
+    /// Parameters:
+    /// - q - the search query
+    /// - type - the type of item to return. One of 'artist', 'album', 'track',
+    ///  'playlist', 'show' or 'episode'
+    /// - market - An ISO 3166-1 alpha-2 country code or the string from_token.
+    /// - include_external: Optional. Possible values: audio.
+    /// - max - the maximum number of items to return
+    /// - dedup - whether to skip the items repeated across pages, which is
+    ///   common for broad queries. More pages are requested until `max`
+    ///   different items are collected. Items without an ID, like local
+    ///   tracks, are always kept.
+    ///
+    /// See [`Self::search`] for more information.
+    async fn search_n(
+        &self,
+        q: &str,
+        _type: SearchType,
+        market: Option<Market>,
+        include_external: Option<IncludeExternal>,
+        max: u32,
+        dedup: bool,
+    ) -> ClientResult<SearchResult> {
+        const MAX_SEARCH_OFFSET: u32 = 1000;
+        const MAX_SEARCH_LIMIT: u32 = 50;
+
+        let mut result = empty_search_result(_type);
+        let max = max.min(MAX_SEARCH_OFFSET);
+        if max == 0 {
+            return Ok(result);
+        }
+
+        let chunk_size = self
+            .get_config()
+            .pagination_chunks
+            .clamp(1, MAX_SEARCH_LIMIT);
+        let mut seen = HashSet::new();
+        let mut offset = 0;
+        let mut collected = 0;
+        loop {
+            let limit = chunk_size
+                .min(max - collected)
+                .min(MAX_SEARCH_OFFSET - offset);
+            let page = self
+                .search(
+                    q,
+                    _type,
+                    market,
+                    include_external,
+                    Some(limit),
+                    Some(offset),
+                )
+                .await?;
+
+            let received = search_result_len(&page);
+            offset += received;
+            let seen = if dedup { Some(&mut seen) } else { None };
+            collected += extend_search_result(&mut result, page, seen);
+
+            // A short page means there are no more results.
+            if received < limit || collected >= max || offset >= MAX_SEARCH_OFFSET {
+                break;
+            }
+        }
+
+        Ok(result)
+    }
+
     /// Get Spotify catalog information about an album's tracks.
     ///
     /// Parameters:
@@ -497,6 +925,12 @@ where
     ///
     /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-users-profile)
     async fn user(&self, user_id: UserId<'_>) -> ClientResult<PublicUser> {
+        let value = self.user_value(user_id).await?;
+        convert_value(value)
+    }
+
+    /// The same as [`Self::user`], but returning the raw JSON response.
+    async fn user_value(&self, user_id: UserId<'_>) -> ClientResult<Value> {
         let url = format!("users/{}", user_id.id());
         let result = self.endpoint_get(&url, &Query::new()).await?;
         convert_result(&result)
@@ -515,6 +949,19 @@ where
         fields: Option<&str>,
         market: Option<Market>,
     ) -> ClientResult<FullPlaylist> {
+        let value = self.playlist_value(playlist_id, fields, market).await?;
+        convert_value(value)
+    }
+
+    /// The same as [`Self::playlist`], but returning the raw JSON response.
+    /// This is also useful along with `fields`, since the filtered response
+    /// may not match [`FullPlaylist`].
+    async fn playlist_value(
+        &self,
+        playlist_id: PlaylistId<'_>,
+        fields: Option<&str>,
+        market: Option<Market>,
+    ) -> ClientResult<Value> {
         let params = build_map([("fields", fields), ("market", market.map(Into::into))]);
 
         let url = format!("playlists/{}", playlist_id.id());
@@ -522,6 +969,23 @@ where
         convert_result(&result)
     }
 
+    /// Get the current cover images of a playlist. Note that brand new
+    /// playlists may have no images, in which case the list is empty.
+    ///
+    /// Parameters:
+    /// - playlist_id - the id of the playlist
+    ///
+    /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-playlist-cover)
+    async fn playlist_cover_image(&self, playlist_id: PlaylistId<'_>) -> ClientResult<Vec<Image>> {
+        let url = format!("playlists/{}/images", playlist_id.id());
+        let result = self.endpoint_get(&url, &Query::new()).await?;
+        if result.is_empty() {
+            Ok(Vec::new())
+        } else {
+            convert_result::<Option<Vec<Image>>>(&result).map(Option::unwrap_or_default)
+        }
+    }
+
     /// Gets playlist of a user.
     ///
     /// Parameters:
@@ -551,7 +1015,10 @@ where
     /// Parameters:
     /// - playlist_id - the id of the playlist
     /// - user_ids - the ids of the users that you want to check to see if they
-    ///   follow the playlist. Maximum: 5 ids.
+    ///   follow the playlist. Maximum: 5 ids, otherwise
+    ///   [`ClientError::TooManyIds`] is returned without sending the request.
+    ///
+    /// The result has the same order as `user_ids`.
     ///
     /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/check-if-user-follows-playlist)
     async fn playlist_check_follow(
@@ -559,10 +1026,12 @@ where
         playlist_id: PlaylistId<'_>,
         user_ids: &[UserId<'_>],
     ) -> ClientResult<Vec<bool>> {
-        debug_assert!(
-            user_ids.len() <= 5,
-            "The maximum length of user ids is limited to 5 :-)"
-        );
+        if user_ids.len() > 5 {
+            return Err(ClientError::TooManyIds {
+                count: user_ids.len(),
+                max: 5,
+            });
+        }
         let url = format!(
             "playlists/{}/followers/contains?ids={}",
             playlist_id.id(),
@@ -582,6 +1051,16 @@ where
     ///
     /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-a-show)
     async fn get_a_show(&self, id: ShowId<'_>, market: Option<Market>) -> ClientResult<FullShow> {
+        let value = self.get_a_show_value(id, market).await?;
+        convert_value(value)
+    }
+
+    /// The same as [`Self::get_a_show`], but returning the raw JSON response.
+    async fn get_a_show_value(
+        &self,
+        id: ShowId<'_>,
+        market: Option<Market>,
+    ) -> ClientResult<Value> {
         let params = build_map([("market", market.map(Into::into))]);
 
         let url = format!("shows/{}", id.id());
@@ -606,7 +1085,7 @@ where
         let params = build_map([("ids", Some(&ids)), ("market", market.map(Into::into))]);
 
         let result = self.endpoint_get("shows", &params).await?;
-        convert_result::<SeversalSimplifiedShows>(&result).map(|x| x.shows)
+        convert_result::<SeveralSimplifiedShows>(&result).map(|x| x.shows)
     }
 
     /// Get Spotify catalog information about an show’s episodes. Optional
@@ -672,6 +1151,17 @@ where
         id: EpisodeId<'_>,
         market: Option<Market>,
     ) -> ClientResult<FullEpisode> {
+        let value = self.get_an_episode_value(id, market).await?;
+        convert_value(value)
+    }
+
+    /// The same as [`Self::get_an_episode`], but returning the raw JSON
+    /// response.
+    async fn get_an_episode_value(
+        &self,
+        id: EpisodeId<'_>,
+        market: Option<Market>,
+    ) -> ClientResult<Value> {
         let url = format!("episodes/{}", id.id());
         let params = build_map([("market", market.map(Into::into))]);
 
@@ -698,19 +1188,30 @@ where
         convert_result::<EpisodesPayload>(&result).map(|x| x.episodes)
     }
 
-    /// Get audio features for a track
+    /// Get audio features for a track. Returns `None` if Spotify has no
+    /// audio features for the track.
     ///
     /// Parameters:
     /// - track - track URI, URL or ID
     ///
     /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-audio-features)
-    async fn track_features(&self, track_id: TrackId<'_>) -> ClientResult<AudioFeatures> {
+    async fn track_features(&self, track_id: TrackId<'_>) -> ClientResult<Option<AudioFeatures>> {
         let url = format!("audio-features/{}", track_id.id());
-        let result = self.endpoint_get(&url, &Query::new()).await?;
-        convert_result(&result)
+        match self.endpoint_get(&url, &Query::new()).await {
+            Ok(result) => convert_result(&result).map(Some),
+            // Spotify responds with a 404 when the track has no audio features
+            Err(ClientError::Http(err)) if err.status_code() == Some(404) => Ok(None),
+            Err(err) => Err(err),
+        }
     }
 
-    /// Get Audio Features for Several Tracks
+    /// Get Audio Features for Several Tracks. The result has one entry per
+    /// track in the same order, which is `None` for the tracks without audio
+    /// features.
+    ///
+    /// Spotify allows up to 100 IDs per request, so the tracks are requested
+    /// in chunks of that size, failing as soon as one of them fails. See
+    /// [`Self::tracks_features_bulk`] to keep the chunks that succeeded.
     ///
     /// Parameters:
     /// - tracks a list of track URIs, URLs or IDs
@@ -719,16 +1220,89 @@ where
     async fn tracks_features<'a>(
         &self,
         track_ids: impl IntoIterator<Item = TrackId<'a>> + Send + 'a,
-    ) -> ClientResult<Option<Vec<AudioFeatures>>> {
-        let url = format!("audio-features/?ids={}", join_ids(track_ids));
+    ) -> ClientResult<Vec<Option<AudioFeatures>>> {
+        let track_ids = track_ids.into_iter().collect::<Vec<_>>();
+
+        let mut features = Vec::with_capacity(track_ids.len());
+        for chunk in track_ids.chunks(100) {
+            let url = format!("audio-features/?ids={}", join_ids(chunk));
+            let result = self.endpoint_get(&url, &Query::new()).await?;
+            if result.is_empty() {
+                features.extend(chunk.iter().map(|_| None));
+            } else {
+                let payload = convert_result::<AudioFeaturesPayload>(&result)?;
+                features.extend(payload.audio_features);
+            }
+        }
 
-        let result = self.endpoint_get(&url, &Query::new()).await?;
-        if result.is_empty() {
-            Ok(None)
-        } else {
-            convert_result::<Option<AudioFeaturesPayload>>(&result)
-                .map(|option_payload| option_payload.map(|x| x.audio_features))
+        Ok(features)
+    }
+
+    /// Get Audio Features for any number of tracks, with as many requests to
+    /// [`Self::tracks_features`] as needed. A chunk that fails, e.g., because
+    /// of a malformed ID, is reported in [`BulkResult::errors`] and the rest
+    /// are still requested.
+    ///
+    /// Parameters:
+    /// - tracks a list of track URIs, URLs or IDs
+    /// - chunk_size - the number of tracks per request, up to the 100 allowed
+    ///   by Spotify, which is the default
+    async fn tracks_features_bulk<'a>(
+        &self,
+        track_ids: impl IntoIterator<Item = TrackId<'a>> + Send + 'a,
+        chunk_size: Option<usize>,
+    ) -> ClientResult<BulkResult<AudioFeatures>> {
+        let chunk_size = validate_chunk_size(chunk_size, 100)?;
+        let track_ids = track_ids.into_iter().collect::<Vec<_>>();
+
+        let mut result = BulkResult {
+            ok: Vec::with_capacity(track_ids.len()),
+            errors: Vec::new(),
+        };
+        for (i, chunk) in track_ids.chunks(chunk_size).enumerate() {
+            let ids = chunk.iter().map(TrackId::as_ref);
+            match self.tracks_features(ids).await {
+                Ok(features) => result.ok.extend(features),
+                Err(err) => {
+                    let start = i * chunk_size;
+                    log::warn!("Audio features chunk at {} failed: {}", start, err);
+                    result.ok.extend(chunk.iter().map(|_| None));
+                    result.errors.push((start..start + chunk.len(), err));
+                }
+            }
         }
+
+        Ok(result)
+    }
+
+    /// Streams the Audio Features of any number of tracks, so that they can be
+    /// processed as soon as each chunk is received, instead of waiting for all
+    /// of them like with [`Self::tracks_features_bulk`]. Each item comes with
+    /// its index in `track_ids`. A chunk that fails yields its error, and the
+    /// following chunks are still requested.
+    ///
+    /// Up to [`Config::stream_concurrency`] chunks are requested at the same
+    /// time in async mode. An invalid `chunk_size` is yielded as the only
+    /// error.
+    ///
+    /// Parameters:
+    /// - tracks a list of track URIs, URLs or IDs
+    /// - chunk_size - the number of tracks per request, up to the 100 allowed
+    ///   by Spotify, which is the default
+    fn tracks_features_stream<'a>(
+        &'a self,
+        track_ids: impl IntoIterator<Item = TrackId<'a>> + Send + 'a,
+        chunk_size: Option<usize>,
+    ) -> Paginator<'a, ClientResult<(usize, Option<AudioFeatures>)>> {
+        let track_ids = track_ids.into_iter().collect::<Vec<_>>();
+        let concurrency = self.get_config().stream_concurrency;
+        paginate_chunks(
+            track_ids.len(),
+            chunk_size,
+            100,
+            concurrency,
+            move |range| self.tracks_features(track_ids[range].to_vec()),
+        )
     }
 
     /// Get Audio Analysis for a Track
@@ -928,9 +1502,14 @@ where
     ///   as `min_acousticness` or `target_duration_ms`.
     /// - seed_artists - a list of artist IDs, URIs or URLs
     /// - seed_tracks - a list of artist IDs, URIs or URLs
-    /// - seed_genres - a list of genre names. Available genres for
+    /// - seed_genres - a list of genre names. The available ones are listed by
+    ///   [`Self::available_genre_seeds`], and they may be checked beforehand
+    ///   with [`validate_genre_seeds`].
     /// - market - An ISO 3166-1 alpha-2 country code or the string from_token. If provided, all
-    ///   results will be playable in this country.
+    ///   results will be playable in this country. Note that `from_token`
+    ///   requires user authorization, so it returns
+    ///   [`ClientError::FromTokenWithoutUser`] with the Client Credentials
+    ///   flow.
     /// - limit - The maximum number of items to return. Default: 20.
     ///   Minimum: 1. Maximum: 100
     /// - min/max/target_<attribute> - For the tuneable track attributes listed
@@ -947,6 +1526,10 @@ where
         market: Option<Market>,
         limit: Option<u32>,
     ) -> ClientResult<Recommendations> {
+        if market == Some(Market::FromToken) && !self.is_user_authorized() {
+            return Err(ClientError::FromTokenWithoutUser);
+        }
+
         let seed_artists = seed_artists.map(join_ids);
         let seed_genres = seed_genres.map(|x| x.into_iter().collect::<Vec<_>>().join(","));
         let seed_tracks = seed_tracks.map(join_ids);
@@ -976,6 +1559,29 @@ where
         convert_result(&result)
     }
 
+    /// Get a list of the genres that can be used as `seed_genres` in
+    /// [`Self::recommendations`].
+    ///
+    /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-recommendation-genres)
+    async fn available_genre_seeds(&self) -> ClientResult<Vec<String>> {
+        let result = self
+            .endpoint_get("recommendations/available-genre-seeds", &Query::new())
+            .await?;
+        convert_result::<GenreSeeds>(&result).map(|x| x.genres)
+    }
+
+    /// Get the list of markets where Spotify is available.
+    ///
+    /// The country codes that aren't known by [`Country`] yet are still
+    /// included, with their [`AvailableMarket::market`] set to `None`.
+    ///
+    /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-available-markets)
+    async fn available_markets(&self) -> ClientResult<Vec<AvailableMarket>> {
+        let result = self.endpoint_get("markets", &Query::new()).await?;
+        let markets = convert_result::<Markets>(&result)?.markets;
+        Ok(markets.into_iter().map(AvailableMarket::from).collect())
+    }
+
     /// Get full details of the items of a playlist owned by a user.
     ///
     /// Parameters:
@@ -1,21 +1,164 @@
 mod base;
 mod oauth;
 pub mod pagination;
+mod player;
 
 pub use base::BaseClient;
 pub use oauth::OAuthClient;
+pub use player::Player;
 
-use crate::ClientResult;
+use crate::{
+    model::{ApiError, Id, Page, SearchResult, SearchType, Token},
+    ClientError, ClientResult,
+};
 
-use std::fmt::Write as _;
+use std::{collections::HashSet, fmt::Write as _};
 
-use serde::Deserialize;
+use maybe_async::maybe_async;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
 
 /// Converts a JSON response from Spotify into its model.
-pub(in crate) fn convert_result<'a, T: Deserialize<'a>>(input: &'a str) -> ClientResult<T> {
+#[cfg(not(feature = "simd"))]
+pub(in crate) fn convert_result<T: DeserializeOwned>(input: &str) -> ClientResult<T> {
     serde_json::from_str::<T>(input).map_err(Into::into)
 }
 
+/// Converts a JSON response from Spotify into its model.
+///
+/// `simd-json` parses the input in place, so it has to be copied first. Its
+/// errors are reported as [`ClientError::ParseJson`] too, so that they can be
+/// handled the same way regardless of the feature.
+#[cfg(feature = "simd")]
+pub(in crate) fn convert_result<T: DeserializeOwned>(input: &str) -> ClientResult<T> {
+    use serde::de::Error as _;
+
+    let mut input = input.as_bytes().to_vec();
+    simd_json::serde::from_slice::<T>(&mut input)
+        .map_err(|err| ClientError::ParseJson(serde_json::Error::custom(err)))
+}
+
+/// Converts a raw JSON response, as returned by the `_value` endpoints, into
+/// its model.
+pub(in crate) fn convert_value<T: DeserializeOwned>(input: Value) -> ClientResult<T> {
+    serde_json::from_value::<T>(input).map_err(Into::into)
+}
+
+/// Checks the number of items to send per request in the methods that split
+/// them up into multiple requests, which is `max` by default.
+pub(in crate) fn validate_chunk_size(
+    chunk_size: Option<usize>,
+    max: usize,
+) -> ClientResult<usize> {
+    match chunk_size {
+        None => Ok(max),
+        Some(size) if (1..=max).contains(&size) => Ok(size),
+        Some(size) => Err(ClientError::InvalidChunkSize { size, max }),
+    }
+}
+
+/// Rejects empty search queries, which Spotify would answer with a `400 Bad
+/// Request`.
+pub(in crate) fn validate_search_query(q: &str) -> ClientResult<()> {
+    if q.trim().is_empty() {
+        Err(ClientError::InvalidRequest(
+            "the search query can't be empty".to_owned(),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Reads the reason of a `404 Not Found` returned by the player endpoints, so
+/// that [`ClientError::NoActiveDevice`] is returned when there's no device to
+/// control. Other 404 errors are returned as [`ClientError::Api`], since the
+/// response can only be read once.
+#[maybe_async]
+pub(in crate) async fn check_active_device(result: ClientResult<String>) -> ClientResult<String> {
+    match result {
+        Err(ClientError::Http(err)) if err.status_code() == Some(404) => {
+            let body = err.into_response_body().await.unwrap_or_default();
+            Err(player_not_found_error(&body))
+        }
+        result => result,
+    }
+}
+
+/// Parses the body of a `404 Not Found` player error, which looks like
+/// `{"error": {"status": 404, "message": "...", "reason": "NO_ACTIVE_DEVICE"}}`.
+fn player_not_found_error(body: &str) -> ClientError {
+    match player_error(404, body) {
+        ApiError::Player { reason, .. } if reason == "NO_ACTIVE_DEVICE" => {
+            ClientError::NoActiveDevice
+        }
+        error => ClientError::Api(error),
+    }
+}
+
+/// Parses the body of a `403 Forbidden` returned when pausing or resuming the
+/// playback, along with whether it's because the playback is already in the
+/// requested state. Spotify reports that either with the `ALREADY_PAUSED` and
+/// `ALREADY_PLAYING` reasons, or with the `UNKNOWN` reason and a "Restriction
+/// violated" message. Other errors, like `PREMIUM_REQUIRED` or a missing
+/// scope, aren't restrictions.
+pub(in crate) fn playback_restriction_error(body: &str, is_playing: bool) -> (ApiError, bool) {
+    let error = player_error(403, body);
+    let already = if is_playing {
+        "ALREADY_PLAYING"
+    } else {
+        "ALREADY_PAUSED"
+    };
+    let restricted = matches!(
+        &error,
+        ApiError::Player { reason, message, .. }
+            if reason == already
+                || (reason == "UNKNOWN" && message.contains("Restriction violated"))
+    );
+
+    (error, restricted)
+}
+
+/// Parses the body of an error returned by the player endpoints, which may
+/// include a `reason`.
+fn player_error(status: u16, body: &str) -> ApiError {
+    let error = serde_json::from_str::<Value>(body).unwrap_or_default();
+    let error = &error["error"];
+    let message = error["message"].as_str().unwrap_or(body).to_owned();
+
+    match error["reason"].as_str() {
+        Some(reason) => ApiError::Player {
+            status,
+            message,
+            reason: reason.to_owned(),
+        },
+        None => ApiError::Regular { status, message },
+    }
+}
+
+/// Spotify may omit the refresh token when refreshing an access token, in
+/// which case the previous one is still valid and has to be kept. Otherwise,
+/// the new one replaces it, since the previous one may have been revoked.
+///
+/// The scopes may be omitted as well, even though they're still granted. They
+/// are taken from the previous token then, or from the requested ones if that
+/// didn't have them either.
+pub(in crate) fn keep_previous_token_fields(
+    token: &mut Token,
+    previous: &Token,
+    requested_scopes: &HashSet<String>,
+) {
+    if token.refresh_token.is_none() {
+        token.refresh_token = previous.refresh_token.clone();
+    }
+    if token.scopes.is_empty() {
+        token.scopes = if previous.scopes.is_empty() {
+            requested_scopes.clone()
+        } else {
+            previous.scopes.clone()
+        };
+    }
+}
+
 /// Append device ID to an API path.
 pub(in crate) fn append_device_id(path: &str, device_id: Option<&str>) -> String {
     let mut new_path = path.to_string();
@@ -29,11 +172,270 @@ pub(in crate) fn append_device_id(path: &str, device_id: Option<&str>) -> String
     new_path
 }
 
+/// Returns a search result of the given type without any items, which can be
+/// extended with [`extend_search_result`].
+pub(in crate) fn empty_search_result(_type: SearchType) -> SearchResult {
+    fn empty<T>() -> Page<T> {
+        Page {
+            href: String::new(),
+            items: Vec::new(),
+            limit: 0,
+            next: None,
+            offset: 0,
+            previous: None,
+            total: 0,
+        }
+    }
+
+    match _type {
+        SearchType::Artist => SearchResult::Artists(empty()),
+        SearchType::Album => SearchResult::Albums(empty()),
+        SearchType::Track => SearchResult::Tracks(empty()),
+        SearchType::Playlist => SearchResult::Playlists(empty()),
+        SearchType::Show => SearchResult::Shows(empty()),
+        SearchType::Episode => SearchResult::Episodes(empty()),
+    }
+}
+
+/// The number of items in a search result page.
+pub(in crate) fn search_result_len(result: &SearchResult) -> u32 {
+    let len = match result {
+        SearchResult::Playlists(page) => page.items.len(),
+        SearchResult::Albums(page) => page.items.len(),
+        SearchResult::Artists(page) => page.items.len(),
+        SearchResult::Tracks(page) => page.items.len(),
+        SearchResult::Shows(page) => page.items.len(),
+        SearchResult::Episodes(page) => page.items.len(),
+    };
+    len as u32
+}
+
+/// Appends the items of a search result page to the accumulated one, returning
+/// how many items were added. Both results must be of the same kind, since
+/// they come from the same search type, and the page is expected to follow
+/// the accumulated one. If the accumulated result is still empty, it's
+/// replaced by the page.
+///
+/// If `seen` is given, the items whose ID is already in it are skipped, and
+/// the IDs of the added ones are inserted, since Spotify may repeat items
+/// across pages. Items without an ID, like local tracks, are always added.
+pub(in crate) fn extend_search_result(
+    acc: &mut SearchResult,
+    next: SearchResult,
+    seen: Option<&mut HashSet<String>>,
+) -> u32 {
+    macro_rules! extend {
+        ($($variant:ident => $id:expr),*) => {
+            match (acc, next) {
+                $(
+                    (SearchResult::$variant(acc), SearchResult::$variant(mut next)) => {
+                        if let Some(seen) = seen {
+                            dedup_page(&mut next, seen, $id);
+                        }
+
+                        let added = next.items.len() as u32;
+                        if acc.items.is_empty() && acc.offset == next.offset {
+                            *acc = next;
+                        } else {
+                            acc.items.extend(next.items);
+                            acc.next = next.next;
+                            acc.total = next.total;
+                        }
+                        added
+                    }
+                )*
+                _ => unreachable!("search result pages of different kinds"),
+            }
+        };
+    }
+
+    extend!(
+        Playlists => |item| Some(item.id.id()),
+        Albums => |item| item.id.as_ref().map(Id::id),
+        Artists => |item| Some(item.id.id()),
+        Tracks => |item| item.id.as_ref().map(Id::id),
+        Shows => |item| Some(item.id.id()),
+        Episodes => |item| Some(item.id.id())
+    )
+}
+
+/// Removes the items of a page whose ID is in `seen`, including those
+/// repeated within the page, and inserts the IDs of the rest.
+fn dedup_page<T>(page: &mut Page<T>, seen: &mut HashSet<String>, id: impl Fn(&T) -> Option<&str>) {
+    page.items.retain(|item| match id(item) {
+        Some(id) => seen.insert(id.to_owned()),
+        None => true,
+    });
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::{model::Token, scopes, ClientCredsSpotify};
+    use crate::{
+        http::{HttpClient, Query},
+        scopes,
+        sync::Mutex,
+        AuthCodeSpotify, ClientCredsSpotify, Config, Credentials,
+    };
     use chrono::{prelude::*, Duration};
+    use std::{
+        io::{BufRead, BufReader, Read, Write as _},
+        net::{TcpListener, TcpStream},
+        sync::Arc,
+        thread,
+    };
+
+    /// Reads a request from the stream, returning its head in lowercase.
+    fn read_request(stream: &TcpStream) -> String {
+        let mut reader = BufReader::new(stream);
+        let mut head = String::new();
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line == "\r\n" {
+                break;
+            }
+            let lowercase = line.to_ascii_lowercase();
+            if let Some(length) = lowercase.strip_prefix("content-length:") {
+                content_length = length.trim().parse().unwrap();
+            }
+            head.push_str(&lowercase);
+        }
+        let mut payload = vec![0; content_length];
+        reader.read_exact(&mut payload).unwrap();
+        head
+    }
+
+    fn write_response(stream: &TcpStream, status: u16, body: &str) {
+        write!(
+            &*stream,
+            "HTTP/1.1 {status} Mock\r\nContent-Type: application/json\r\n\
+             Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+    }
+
+    /// Serves the given responses in order on a local port, one per
+    /// connection. Returns the prefix to be configured in the client, and the
+    /// head of each request received, e.g., to check its headers.
+    fn mock_server(
+        responses: Vec<(u16, &'static str)>,
+    ) -> (String, Arc<std::sync::Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let prefix = format!("http://{}/v1/", listener.local_addr().unwrap());
+        let requests = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let received = Arc::clone(&requests);
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let head = read_request(&stream);
+                received.lock().unwrap().push(head);
+                write_response(&stream, status, body);
+            }
+        });
+
+        (prefix, requests)
+    }
+
+    /// Like [`mock_server`], but it doesn't respond until all the requests
+    /// have been received, so they must be sent concurrently.
+    fn concurrent_mock_server(
+        responses: Vec<(u16, &'static str)>,
+    ) -> (String, Arc<std::sync::Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let prefix = format!("http://{}/v1/", listener.local_addr().unwrap());
+        let requests = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let received = Arc::clone(&requests);
+        thread::spawn(move || {
+            let streams = responses
+                .iter()
+                .map(|_| {
+                    let (stream, _) = listener.accept().unwrap();
+                    let head = read_request(&stream);
+                    received.lock().unwrap().push(head);
+                    stream
+                })
+                .collect::<Vec<_>>();
+            for (stream, (status, body)) in streams.iter().zip(responses) {
+                write_response(stream, status, body);
+            }
+        });
+
+        (prefix, requests)
+    }
+
+    /// A client with a token that sends its requests to the given prefix.
+    fn mock_client(prefix: String) -> AuthCodeSpotify {
+        let mut spotify = AuthCodeSpotify::from_token(Token {
+            access_token: "test-access_token".to_owned(),
+            ..Default::default()
+        });
+        spotify.config.prefix = prefix;
+        spotify
+    }
+
+    /// A client whose token is refetched without making any requests, which
+    /// fails if there's no token to be returned.
+    #[derive(Clone, Debug, Default)]
+    struct RefetchClient {
+        config: Config,
+        creds: Credentials,
+        token: Arc<Mutex<Option<Token>>>,
+        http: HttpClient,
+        refetched: Option<Token>,
+    }
+
+    #[maybe_async]
+    impl BaseClient for RefetchClient {
+        fn get_config(&self) -> &Config {
+            &self.config
+        }
+
+        fn get_config_mut(&mut self) -> &mut Config {
+            &mut self.config
+        }
+
+        fn get_http(&self) -> &HttpClient {
+            &self.http
+        }
+
+        fn get_creds(&self) -> &Credentials {
+            &self.creds
+        }
+
+        fn get_token(&self) -> Arc<Mutex<Option<Token>>> {
+            Arc::clone(&self.token)
+        }
+
+        async fn refetch_token(&self) -> ClientResult<Option<Token>> {
+            match &self.refetched {
+                Some(token) => Ok(Some(token.clone())),
+                None => Err(ClientError::InvalidRequest(
+                    "no token to refetch".to_owned(),
+                )),
+            }
+        }
+    }
+
+    fn refetch_client(prefix: String, refresh: bool, refetched: Option<&str>) -> RefetchClient {
+        let token = |access_token: &str| Token {
+            access_token: access_token.to_owned(),
+            ..Default::default()
+        };
+
+        RefetchClient {
+            config: Config {
+                prefix,
+                token_refresh_on_unauthorized: refresh,
+                ..Default::default()
+            },
+            token: Arc::new(Mutex::new(Some(token("expired")))),
+            refetched: refetched.map(token),
+            ..Default::default()
+        }
+    }
 
     #[test]
     fn test_append_device_id_without_question_mark() {
@@ -54,6 +456,38 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_validate_chunk_size() {
+        assert_eq!(validate_chunk_size(None, 100).unwrap(), 100);
+        assert_eq!(validate_chunk_size(Some(20), 100).unwrap(), 20);
+        assert_eq!(validate_chunk_size(Some(100), 100).unwrap(), 100);
+        assert!(matches!(
+            validate_chunk_size(Some(0), 100),
+            Err(ClientError::InvalidChunkSize { size: 0, max: 100 })
+        ));
+        assert!(matches!(
+            validate_chunk_size(Some(101), 100),
+            Err(ClientError::InvalidChunkSize {
+                size: 101,
+                max: 100
+            })
+        ));
+    }
+
+    #[test]
+    fn test_validate_search_query() {
+        assert!(validate_search_query("weezer").is_ok());
+        assert!(validate_search_query(" album:arrival ").is_ok());
+        assert!(matches!(
+            validate_search_query(""),
+            Err(ClientError::InvalidRequest(_))
+        ));
+        assert!(matches!(
+            validate_search_query(" \t\n"),
+            Err(ClientError::InvalidRequest(_))
+        ));
+    }
+
     #[test]
     fn test_endpoint_url() {
         let spotify = ClientCredsSpotify::default();
@@ -71,6 +505,387 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_player_not_found_error() {
+        let body = r#"{"error": {"status": 404, "message": "Player command failed: No active device found", "reason": "NO_ACTIVE_DEVICE"}}"#;
+        assert!(matches!(
+            player_not_found_error(body),
+            ClientError::NoActiveDevice
+        ));
+
+        let body =
+            r#"{"error": {"status": 404, "message": "Device not found", "reason": "UNKNOWN"}}"#;
+        assert!(matches!(
+            player_not_found_error(body),
+            ClientError::Api(ApiError::Player { status: 404, message, reason })
+                if message == "Device not found" && reason == "UNKNOWN"
+        ));
+
+        assert!(matches!(
+            player_not_found_error("Not found"),
+            ClientError::Api(ApiError::Regular { status: 404, message }) if message == "Not found"
+        ));
+    }
+
+    #[test]
+    fn test_playback_restriction_error() {
+        let body = r#"{"error": {"status": 403, "message": "Player command failed: Restriction violated", "reason": "UNKNOWN"}}"#;
+        assert!(playback_restriction_error(body, false).1);
+        assert!(playback_restriction_error(body, true).1);
+
+        let body = r#"{"error": {"status": 403, "message": "Player command failed: Already paused", "reason": "ALREADY_PAUSED"}}"#;
+        assert!(playback_restriction_error(body, false).1);
+        assert!(!playback_restriction_error(body, true).1);
+
+        let body = r#"{"error": {"status": 403, "message": "Player command failed: Premium required", "reason": "PREMIUM_REQUIRED"}}"#;
+        let (error, restricted) = playback_restriction_error(body, false);
+        assert!(!restricted);
+        assert!(matches!(
+            error,
+            ApiError::Player { status: 403, reason, .. } if reason == "PREMIUM_REQUIRED"
+        ));
+
+        let body = r#"{"error": {"status": 403, "message": "Insufficient client scope"}}"#;
+        let (error, restricted) = playback_restriction_error(body, false);
+        assert!(!restricted);
+        assert!(matches!(error, ApiError::Regular { status: 403, .. }));
+    }
+
+    #[test]
+    fn test_keep_previous_token_fields() {
+        let previous = Token {
+            refresh_token: Some("previous".to_owned()),
+            scopes: scopes!("playlist-read-private"),
+            ..Default::default()
+        };
+        let requested = scopes!("playlist-read-private", "user-read-email");
+
+        let response = r#"{"access_token": "new", "token_type": "Bearer", "expires_in": 3600}"#;
+        let mut token = serde_json::from_str::<Token>(response).unwrap();
+        keep_previous_token_fields(&mut token, &previous, &requested);
+        assert_eq!(token.refresh_token.as_deref(), Some("previous"));
+        assert_eq!(token.scopes, previous.scopes);
+
+        let response = r#"{"access_token": "new", "token_type": "Bearer", "expires_in": 3600, "scope": "user-read-email", "refresh_token": "rotated"}"#;
+        let mut token = serde_json::from_str::<Token>(response).unwrap();
+        keep_previous_token_fields(&mut token, &previous, &requested);
+        assert_eq!(token.refresh_token.as_deref(), Some("rotated"));
+        assert_eq!(token.scopes, scopes!("user-read-email"));
+
+        // Tokens without scopes, like those created manually, fall back to the
+        // requested ones
+        let response = r#"{"access_token": "new", "token_type": "Bearer", "expires_in": 3600}"#;
+        let mut token = serde_json::from_str::<Token>(response).unwrap();
+        keep_previous_token_fields(&mut token, &Token::default(), &requested);
+        assert_eq!(token.scopes, requested);
+    }
+
+    #[test]
+    fn test_dedup_page() {
+        let page = |items| Page {
+            href: String::new(),
+            items,
+            limit: 7,
+            next: None,
+            offset: 0,
+            previous: None,
+            total: 10,
+        };
+
+        let mut seen = HashSet::new();
+        let mut first = page(vec![Some("1"), Some("2"), None, Some("1"), None, Some("3")]);
+        dedup_page(&mut first, &mut seen, |item| *item);
+        assert_eq!(first.items, [Some("1"), Some("2"), None, None, Some("3")]);
+        assert_eq!(first.limit, 7);
+
+        // The items seen in previous pages are skipped too
+        let mut second = page(vec![Some("2"), None, Some("4")]);
+        dedup_page(&mut second, &mut seen, |item| *item);
+        assert_eq!(second.items, [None, Some("4")]);
+    }
+
+    #[test]
+    fn test_extend_search_result() {
+        let page = |offset| Page {
+            href: format!("https://api.spotify.com/v1/search?offset={offset}"),
+            items: Vec::new(),
+            limit: 10,
+            next: Some(format!(
+                "https://api.spotify.com/v1/search?offset={}",
+                offset + 10
+            )),
+            offset,
+            previous: None,
+            total: 30,
+        };
+
+        // The first page replaces the empty result
+        let mut result = empty_search_result(SearchType::Track);
+        let added = extend_search_result(&mut result, SearchResult::Tracks(page(0)), None);
+        assert_eq!(added, 0);
+        assert_eq!(result, SearchResult::Tracks(page(0)));
+
+        // The next ones are appended, taking their `next` link
+        let added = extend_search_result(&mut result, SearchResult::Tracks(page(10)), None);
+        assert_eq!(added, 0);
+        match result {
+            SearchResult::Tracks(result) => {
+                assert_eq!(result.offset, 0);
+                assert_eq!(result.limit, 10);
+                assert_eq!(result.next, page(10).next);
+            }
+            _ => panic!("expected a page of tracks"),
+        }
+    }
+
+    #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+    async fn test_search_n_dedup() {
+        // The second page only repeats an artist, so another one is requested
+        // to collect the three that were asked for
+        let (prefix, requests) = mock_server(vec![
+            (
+                200,
+                r#"{"artists": {"href": "", "limit": 2, "next": null, "offset": 0, "previous": null, "total": 10, "items": [
+                    {"external_urls": {}, "genres": [], "href": "", "id": "0OdUWJ0sBjDrqHygGUXeCF", "images": [], "name": "A"},
+                    {"external_urls": {}, "genres": [], "href": "", "id": "3jOstUTkEu2JkjvRdBA5Gu", "images": [], "name": "B"}
+                ]}}"#,
+            ),
+            (
+                200,
+                r#"{"artists": {"href": "", "limit": 1, "next": null, "offset": 2, "previous": null, "total": 10, "items": [
+                    {"external_urls": {}, "genres": [], "href": "", "id": "0OdUWJ0sBjDrqHygGUXeCF", "images": [], "name": "A"}
+                ]}}"#,
+            ),
+            (
+                200,
+                r#"{"artists": {"href": "", "limit": 1, "next": null, "offset": 3, "previous": null, "total": 10, "items": [
+                    {"external_urls": {}, "genres": [], "href": "", "id": "4tZwfgrHOc3mvqYlEYSvVi", "images": [], "name": "C"}
+                ]}}"#,
+            ),
+        ]);
+        let mut spotify = mock_client(prefix);
+        spotify.config.pagination_chunks = 2;
+
+        let result = spotify
+            .search_n("abba", SearchType::Artist, None, None, 3, true)
+            .await
+            .unwrap();
+        let names = match result {
+            SearchResult::Artists(page) => page
+                .items
+                .into_iter()
+                .map(|artist| artist.name)
+                .collect::<Vec<_>>(),
+            _ => panic!("expected a page of artists"),
+        };
+        assert_eq!(names, ["A", "B", "C"]);
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 3);
+        assert!(requests[1].contains("offset=2"));
+        assert!(requests[2].contains("offset=3"));
+    }
+
+    #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+    async fn test_refresh_on_unauthorized() {
+        let (prefix, requests) = mock_server(vec![(401, ""), (200, "{}")]);
+        let spotify = refetch_client(prefix, true, Some("refreshed"));
+
+        let result = spotify.endpoint_get("me", &Query::new()).await;
+        assert_eq!(result.unwrap(), "{}");
+        let token = spotify.token().await.unwrap();
+        assert_eq!(token.access_token, "refreshed");
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].contains("authorization: bearer expired"));
+        assert!(requests[1].contains("authorization: bearer refreshed"));
+    }
+
+    #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+    async fn test_refresh_on_unauthorized_disabled() {
+        let (prefix, requests) = mock_server(vec![(401, "")]);
+        let spotify = refetch_client(prefix, false, Some("refreshed"));
+
+        let result = spotify.endpoint_get("me", &Query::new()).await;
+        assert!(matches!(result, Err(ClientError::Http(err)) if err.status_code() == Some(401)));
+        let token = spotify.token().await.unwrap();
+        assert_eq!(token.access_token, "expired");
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
+    #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+    async fn test_refresh_on_unauthorized_failing() {
+        // The token can't be refetched
+        let (prefix, requests) = mock_server(vec![(401, "")]);
+        let spotify = refetch_client(prefix, true, None);
+
+        let result = spotify.endpoint_get("me", &Query::new()).await;
+        assert!(matches!(result, Err(ClientError::Http(err)) if err.status_code() == Some(401)));
+        assert_eq!(requests.lock().unwrap().len(), 1);
+
+        // The refetched token can't be written to the cache
+        let (prefix, requests) = mock_server(vec![(401, "")]);
+        let mut spotify = refetch_client(prefix, true, Some("refreshed"));
+        spotify.config.token_cached = true;
+        spotify.config.cache_path = "/nonexistent/.spotify_token_cache.json".into();
+
+        let result = spotify.endpoint_get("me", &Query::new()).await;
+        assert!(matches!(result, Err(ClientError::Http(err)) if err.status_code() == Some(401)));
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
+    const RESTRICTION_VIOLATED: &str = r#"{"error": {"status": 403, "message": "Player command failed: Restriction violated", "reason": "UNKNOWN"}}"#;
+
+    fn idempotent_client(prefix: String) -> AuthCodeSpotify {
+        let mut spotify = mock_client(prefix);
+        spotify.config.idempotent_playback = true;
+        spotify
+    }
+
+    #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+    async fn test_idempotent_pause() {
+        let paused = r#"{
+            "device": {
+                "id": "28d0f845293d03a2713392905c6d44ee",
+                "is_active": true,
+                "is_private_session": false,
+                "is_restricted": false,
+                "name": "Web Player",
+                "type": "Computer",
+                "volume_percent": 100
+            },
+            "repeat_state": "off",
+            "shuffle_state": false,
+            "context": null,
+            "timestamp": 1607769168429,
+            "progress_ms": 2000,
+            "is_playing": false,
+            "item": null,
+            "currently_playing_type": "unknown",
+            "actions": {"disallows": {"pausing": true}}
+        }"#;
+        let (prefix, requests) = mock_server(vec![(403, RESTRICTION_VIOLATED), (200, paused)]);
+        let spotify = idempotent_client(prefix);
+
+        spotify.pause_playback(None).await.unwrap();
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].starts_with("put /v1/me/player/pause"));
+        assert!(requests[1].starts_with("get /v1/me/player"));
+    }
+
+    #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+    async fn test_idempotent_pause_other_error() {
+        // Not a restriction, so the playback isn't checked
+        let premium_required = r#"{"error": {"status": 403, "message": "Player command failed: Premium required", "reason": "PREMIUM_REQUIRED"}}"#;
+        let (prefix, requests) = mock_server(vec![(403, premium_required)]);
+        let spotify = idempotent_client(prefix);
+
+        let result = spotify.pause_playback(None).await;
+        assert!(matches!(
+            result,
+            Err(ClientError::Api(ApiError::Player { status: 403, reason, .. }))
+                if reason == "PREMIUM_REQUIRED"
+        ));
+        assert_eq!(requests.lock().unwrap().len(), 1);
+
+        // The playback can't be checked, so the original error is kept
+        let (prefix, requests) = mock_server(vec![(403, RESTRICTION_VIOLATED), (500, "")]);
+        let spotify = idempotent_client(prefix);
+
+        let result = spotify.pause_playback(None).await;
+        assert!(matches!(
+            result,
+            Err(ClientError::Api(ApiError::Player { status: 403, reason, .. }))
+                if reason == "UNKNOWN"
+        ));
+        assert_eq!(requests.lock().unwrap().len(), 2);
+    }
+
+    /// The chunks are requested concurrently, but still yielded in order. The
+    /// server only responds once both requests are received, so it would time
+    /// out if they were sent one after another.
+    #[cfg(feature = "__async")]
+    #[tokio::test]
+    async fn test_paginate_chunks_concurrently() {
+        use futures::StreamExt;
+
+        let (prefix, requests) = concurrent_mock_server(vec![
+            (200, r#"{"audio_features": [null, null]}"#),
+            (200, r#"{"audio_features": [null, null]}"#),
+        ]);
+        let mut spotify = mock_client(prefix);
+        spotify.config.stream_concurrency = 2;
+        let ids = ["4JpKVNYnVcJ8tuMKjAj50A", "24JygzOLM0EmRQeGtFcIcG"]
+            .iter()
+            .cycle()
+            .take(4)
+            .map(|id| crate::model::TrackId::from_id(*id).unwrap());
+
+        let indices = spotify
+            .tracks_features_stream(ids, Some(2))
+            .map(|item| item.unwrap().0)
+            .collect::<Vec<_>>();
+        let indices = tokio::time::timeout(std::time::Duration::from_secs(5), indices)
+            .await
+            .expect("the chunks weren't requested concurrently");
+        assert_eq!(indices, [0, 1, 2, 3]);
+        assert_eq!(requests.lock().unwrap().len(), 2);
+    }
+
+    #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+    async fn test_tracks_features_chunks() {
+        // The second chunk has a single track, and its response is empty
+        let features = format!(r#"{{"audio_features": [{}]}}"#, ["null"; 100].join(","));
+        let features = Box::leak(features.into_boxed_str());
+        let (prefix, requests) = mock_server(vec![(200, features), (200, "")]);
+        let spotify = mock_client(prefix);
+        let ids = std::iter::repeat("4JpKVNYnVcJ8tuMKjAj50A")
+            .take(101)
+            .map(|id| crate::model::TrackId::from_id(id).unwrap());
+
+        let features = spotify.tracks_features(ids).await.unwrap();
+        assert_eq!(features.len(), 101);
+        assert!(features.iter().all(Option::is_none));
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].matches("4jpkvnynvcj8tumkjaj50a").count(), 100);
+        assert_eq!(requests[1].matches("4jpkvnynvcj8tumkjaj50a").count(), 1);
+    }
+
+    #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+    async fn test_volume_not_supported() {
+        let devices = r#"{
+            "devices": [{
+                "id": "28d0f845293d03a2713392905c6d44ee",
+                "is_active": true,
+                "is_private_session": false,
+                "is_restricted": false,
+                "name": "Living room TV",
+                "type": "TV",
+                "volume_percent": null,
+                "supports_volume": false
+            }]
+        }"#;
+        let device_id = Some("28d0f845293d03a2713392905c6d44ee");
+
+        // The device isn't checked by default
+        let (prefix, requests) = mock_server(vec![(204, "")]);
+        let spotify = mock_client(prefix);
+        spotify.volume(50, device_id).await.unwrap();
+        assert_eq!(requests.lock().unwrap().len(), 1);
+
+        let (prefix, requests) = mock_server(vec![(200, devices)]);
+        let mut spotify = mock_client(prefix);
+        spotify.config.check_volume_support = true;
+        let result = spotify.volume(50, device_id).await;
+        assert!(matches!(result, Err(ClientError::VolumeNotSupported)));
+        assert_eq!(requests.lock().unwrap().len(), 1);
+        assert!(requests.lock().unwrap()[0].starts_with("get /v1/me/player/devices"));
+    }
+
     #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
     async fn test_auth_headers() {
         let tok = Token {
@@ -88,4 +903,34 @@ mod test {
             Some(&"Bearer test-access_token".to_owned())
         );
     }
+
+    #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+    async fn test_auth_headers_accept_language() {
+        let tok = Token {
+            access_token: "test-access_token".to_string(),
+            expires_in: Duration::seconds(1),
+            expires_at: Some(Utc::now()),
+            scopes: scopes!("playlist-read-private"),
+            refresh_token: Some("...".to_string()),
+        };
+
+        let mut spotify = ClientCredsSpotify::from_token(tok);
+        let headers = spotify.auth_headers().await;
+        assert_eq!(headers.get("accept-language"), None);
+
+        spotify.config = Config::default().with_accept_language("es-ES").unwrap();
+        let headers = spotify.auth_headers().await;
+        assert_eq!(headers.get("accept-language"), Some(&"es-ES".to_owned()));
+    }
+
+    #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+    async fn test_invalid_header() {
+        let mut spotify = AuthCodeSpotify::from_token(Token {
+            access_token: "test\naccess_token".to_owned(),
+            ..Default::default()
+        });
+        spotify.config.prefix = "http://127.0.0.1:1/v1/".to_owned();
+        let result = spotify.current_user().await;
+        assert!(matches!(result, Err(ClientError::Http(_))));
+    }
 }
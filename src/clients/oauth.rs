@@ -1,17 +1,22 @@
 use crate::{
     clients::{
-        append_device_id, convert_result,
+        append_device_id, check_active_device, convert_result, convert_value,
         pagination::{paginate, Paginator},
-        BaseClient,
+        playback_restriction_error, validate_chunk_size, BaseClient, Player,
     },
     http::Query,
     join_ids,
     model::*,
+    sync::Mutex,
     util::{build_map, JsonBuilder},
-    ClientResult, OAuth, Token,
+    ClientError, ClientResult, OAuth, Token,
 };
 
-use std::{collections::HashMap, time};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time,
+};
 
 use maybe_async::maybe_async;
 use rspotify_model::idtypes::{PlayContextId, PlayableId};
@@ -31,6 +36,14 @@ use url::Url;
 pub trait OAuthClient: BaseClient {
     fn get_oauth(&self) -> &OAuth;
 
+    /// The cache used by [`Self::user_market`], which stores the market along
+    /// with the access token it was requested with. By default, a new one is
+    /// returned every time, so the market isn't cached.
+    #[doc(hidden)]
+    fn get_user_market_cache(&self) -> Arc<Mutex<Option<(String, Market)>>> {
+        Arc::new(Mutex::new(None))
+    }
+
     /// Obtains a user access token given a code, as part of the OAuth
     /// authentication. The access token will be saved internally.
     async fn request_token(&self, code: &str) -> ClientResult<()>;
@@ -64,9 +77,7 @@ pub trait OAuthClient: BaseClient {
 
         log::info!("Reading auth token cache");
         let token = Token::from_cache(&self.get_config().cache_path)?;
-        if !self.get_oauth().scopes.is_subset(&token.scopes)
-            || (!allow_expired && token.is_expired())
-        {
+        if self.get_oauth().needs_reauth(&token) || (!allow_expired && token.is_expired()) {
             // Invalid token, since it doesn't have at least the currently
             // required scopes or it's expired.
             Ok(None)
@@ -75,28 +86,61 @@ pub trait OAuthClient: BaseClient {
         }
     }
 
-    /// Parse the response code in the given response url. If the URL cannot be
-    /// parsed or the `code` parameter is not present, this will return `None`.
+    /// Checks that the current token has been granted the given scope, so that
+    /// a missing scope can be reported with [`ClientError::MissingScope`]
+    /// before sending a request, instead of Spotify responding with `403
+    /// Forbidden`.
+    async fn require_scope(&self, scope: Scope) -> ClientResult<()> {
+        let granted = self
+            .get_token()
+            .lock()
+            .await
+            .unwrap()
+            .as_ref()
+            .map_or(false, |token| token.has_scope(scope));
+
+        if granted {
+            Ok(())
+        } else {
+            Err(ClientError::MissingScope(scope))
+        }
+    }
+
+    /// Parse the response code in the given response url, failing with
+    /// [`ClientError::StateMismatch`] if its `state` isn't the one in the
+    /// authorization URL, i.e., [`OAuth::state`]. This protects against CSRF,
+    /// since the response may not come from the request made by the app.
     ///
+    /// If the state matches, it fails with [`ClientError::AuthorizationDenied`]
+    /// when Spotify responded with an error, e.g., if the user denied access,
+    /// and with [`ClientError::InvalidRequest`] if the `code` parameter is
+    /// missing.
     // As the [RFC
     // indicates](https://datatracker.ietf.org/doc/html/rfc6749#section-4.1),
-    // the state should be the same between the request and the callback. This
-    // will also return `None` if this is not true.
-    fn parse_response_code(&self, url: &str) -> Option<String> {
-        let url = Url::parse(url).ok()?;
+    // the state should be the same between the request and the callback.
+    fn parse_response_code(&self, url: &str) -> ClientResult<String> {
+        let url = Url::parse(url)?;
         let params = url.query_pairs().collect::<HashMap<_, _>>();
 
-        let code = params.get("code")?;
-
-        // Making sure the state is the same
+        // Making sure the state is the same before anything else, so that a
+        // forged response can't be reported as something else
         let expected_state = &self.get_oauth().state;
         let state = params.get("state").map(AsRef::as_ref);
         if state != Some(expected_state) {
-            log::error!("Request state doesn't match the callback state");
-            return None;
+            return Err(ClientError::StateMismatch);
+        }
+
+        // Spotify sends an `error` parameter instead of the code when the
+        // authorization failed, e.g., if the user denied access.
+        if let Some(error) = params.get("error") {
+            return Err(ClientError::AuthorizationDenied(error.to_string()));
         }
 
-        Some(code.to_string())
+        let code = params.get("code").ok_or_else(|| {
+            ClientError::InvalidRequest("the response doesn't include a code".to_owned())
+        })?;
+
+        Ok(code.to_string())
     }
 
     /// Tries to open the authorization URL in the user's browser, and returns
@@ -105,8 +149,6 @@ pub trait OAuthClient: BaseClient {
     /// Note: this method requires the `cli` feature.
     #[cfg(feature = "cli")]
     fn get_code_from_user(&self, url: &str) -> ClientResult<String> {
-        use crate::ClientError;
-
         log::info!("Opening brower with auth URL");
         match webbrowser::open(url) {
             Ok(_) => println!("Opened {} in your browser.", url),
@@ -121,11 +163,7 @@ pub trait OAuthClient: BaseClient {
         println!("Please enter the URL you were redirected to: ");
         let mut input = String::new();
         std::io::stdin().read_line(&mut input)?;
-        let code = self
-            .parse_response_code(&input)
-            .ok_or_else(|| ClientError::Cli("unable to parse the response code".to_string()))?;
-
-        Ok(code)
+        self.parse_response_code(input.trim())
     }
 
     /// Opens up the authorization URL in the user's browser so that it can
@@ -247,6 +285,46 @@ pub trait OAuthClient: BaseClient {
         convert_result(&result)
     }
 
+    /// Changes a playlist's name, description and/or public/collaborative
+    /// state. Only the given fields will be modified.
+    ///
+    /// Parameters:
+    /// - playlist_id - the id of the playlist
+    /// - name - optional name of the playlist
+    /// - public - optional is the playlist public
+    /// - collaborative - optional is the playlist collaborative. Note: to make
+    ///   a playlist collaborative you must also set public to false
+    /// - description - optional description of the playlist
+    ///
+    /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/change-playlist-details)
+    async fn playlist_change_details(
+        &self,
+        playlist_id: PlaylistId<'_>,
+        name: Option<&str>,
+        public: Option<bool>,
+        collaborative: Option<bool>,
+        description: Option<&str>,
+    ) -> ClientResult<()> {
+        debug_assert!(
+            !(collaborative.unwrap_or(false) && public.unwrap_or(false)),
+            "To make a playlist collaborative you must also set public to \
+            false. See the reference for more information."
+        );
+
+        let body = ChangeDetailsBody {
+            name,
+            public,
+            collaborative,
+            description,
+        };
+        let params = serde_json::to_value(body)?;
+
+        let url = format!("playlists/{}", playlist_id.id());
+        self.endpoint_put(&url, &params).await?;
+
+        Ok(())
+    }
+
     /// Changes a playlist's name and/or public/private state.
     ///
     /// Parameters:
@@ -257,6 +335,7 @@ pub trait OAuthClient: BaseClient {
     /// - description - optional description of the playlist
     ///
     /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/change-playlist-details)
+    #[deprecated(note = "Please use `playlist_change_details` instead")]
     async fn playlist_change_detail(
         &self,
         playlist_id: PlaylistId<'_>,
@@ -265,12 +344,13 @@ pub trait OAuthClient: BaseClient {
         description: Option<&str>,
         collaborative: Option<bool>,
     ) -> ClientResult<String> {
-        let params = JsonBuilder::new()
-            .optional("name", name)
-            .optional("public", public)
-            .optional("collaborative", collaborative)
-            .optional("description", description)
-            .build();
+        let body = ChangeDetailsBody {
+            name,
+            public,
+            collaborative,
+            description,
+        };
+        let params = serde_json::to_value(body)?;
 
         let url = format!("playlists/{}", playlist_id.id());
         self.endpoint_put(&url, &params).await
@@ -314,6 +394,122 @@ pub trait OAuthClient: BaseClient {
         convert_result(&result)
     }
 
+    /// Adds tracks to a playlist, skipping the ones that are already in it (or
+    /// that are repeated in `track_ids`). All the pages of the playlist are
+    /// fetched first in order to know its current tracks.
+    ///
+    /// Returns the tracks that were added and the ones that were skipped, in
+    /// the same order as they were given.
+    ///
+    /// Parameters:
+    /// - playlist_id - the id of the playlist
+    /// - track_ids - a list of track URIs, URLs or IDs
+    /// - chunk_size - the number of tracks added per request, up to the 100
+    ///   allowed by Spotify, which is the default
+    ///
+    /// See [`Self::playlist_add_items`] for more information.
+    async fn playlist_add_tracks_dedup<'a>(
+        &self,
+        playlist_id: PlaylistId<'_>,
+        track_ids: impl IntoIterator<Item = TrackId<'a>> + Send + 'a,
+        chunk_size: Option<usize>,
+    ) -> ClientResult<(Vec<TrackId<'static>>, Vec<TrackId<'static>>)> {
+        let chunk_size = validate_chunk_size(chunk_size, 100)?;
+        let limit = self.get_config().pagination_chunks;
+        let mut existing = HashSet::new();
+        let mut offset = 0;
+        loop {
+            let page = self
+                .playlist_items_manual(playlist_id.as_ref(), None, None, Some(limit), Some(offset))
+                .await?;
+            let ids = page
+                .items
+                .iter()
+                .filter_map(|item| match item.spotify_id()? {
+                    PlayableId::Track(id) => Some(id.into_static()),
+                    PlayableId::Episode(_) => None,
+                });
+            existing.extend(ids);
+
+            if page.next.is_none() {
+                break;
+            }
+            offset += limit;
+        }
+
+        let mut added = Vec::new();
+        let mut skipped = Vec::new();
+        for id in track_ids {
+            let id = id.into_static();
+            if existing.insert(id.clone()) {
+                added.push(id);
+            } else {
+                skipped.push(id);
+            }
+        }
+
+        for chunk in added.chunks(chunk_size) {
+            let items = chunk.iter().map(|id| PlayableId::Track(id.as_ref()));
+            self.playlist_add_items(playlist_id.as_ref(), items, None)
+                .await?;
+        }
+
+        Ok((added, skipped))
+    }
+
+    /// Creates a playlist for a user and adds the given tracks to it, in
+    /// chunks of 100 tracks. Returns the playlist once it's been filled, so
+    /// that its tracks are included.
+    ///
+    /// If a request fails after the playlist has been created, the error is
+    /// [`ClientError::PlaylistNotFilled`], which includes the ID of the new
+    /// playlist and how many tracks were added to it, so that it can be
+    /// completed or removed instead of being left behind.
+    ///
+    /// Parameters:
+    /// - user_id - the id of the user
+    /// - name - the name of the playlist
+    /// - public - is the created playlist public
+    /// - track_ids - a list of track URIs, URLs or IDs
+    ///
+    /// See [`Self::user_playlist_create`] and [`Self::playlist_add_items`]
+    /// for more information.
+    async fn user_playlist_create_with_tracks<'a>(
+        &self,
+        user_id: UserId<'_>,
+        name: &str,
+        public: Option<bool>,
+        track_ids: impl IntoIterator<Item = TrackId<'a>> + Send + 'a,
+    ) -> ClientResult<FullPlaylist> {
+        let track_ids = track_ids.into_iter().collect::<Vec<_>>();
+        let playlist = self
+            .user_playlist_create(user_id, name, public, None, None)
+            .await?;
+
+        let mut added = 0;
+        for chunk in track_ids.chunks(100) {
+            let items = chunk.iter().map(|id| PlayableId::Track(id.as_ref()));
+            let result = self
+                .playlist_add_items(playlist.id.as_ref(), items, None)
+                .await;
+            if let Err(err) = result {
+                return Err(ClientError::PlaylistNotFilled {
+                    playlist_id: playlist.id,
+                    added,
+                    source: Box::new(err),
+                });
+            }
+            added += chunk.len();
+        }
+
+        let result = self.playlist(playlist.id.as_ref(), None, None).await;
+        result.map_err(|err| ClientError::PlaylistNotFilled {
+            playlist_id: playlist.id,
+            added,
+            source: Box::new(err),
+        })
+    }
+
     /// Replace all items in a playlist
     ///
     /// Parameters:
@@ -356,12 +552,13 @@ pub trait OAuthClient: BaseClient {
         range_length: Option<u32>,
         snapshot_id: Option<&str>,
     ) -> ClientResult<PlaylistResult> {
-        let params = JsonBuilder::new()
-            .optional("range_start", range_start)
-            .optional("insert_before", insert_before)
-            .optional("range_length", range_length)
-            .optional("snapshot_id", snapshot_id)
-            .build();
+        let body = ReorderBody {
+            range_start,
+            insert_before,
+            range_length,
+            snapshot_id,
+        };
+        let params = serde_json::to_value(body)?;
 
         let url = format!("playlists/{}/tracks", playlist_id.id());
         let result = self.endpoint_put(&url, &params).await?;
@@ -456,6 +653,82 @@ pub trait OAuthClient: BaseClient {
         convert_result(&result)
     }
 
+    /// Removes all the items of a playlist that match the given predicate, such
+    /// as the explicit tracks or the ones by some artist. Returns the snapshot
+    /// ID of the playlist after the removal.
+    ///
+    /// The matching items are removed by their position, starting from the
+    /// end of the playlist so that every request leaves the positions of the
+    /// remaining ones untouched. Local files are skipped, since they can't be
+    /// removed by their URI.
+    ///
+    /// Parameters:
+    /// - playlist_id - the id of the playlist
+    /// - pred - returns true for the items to be removed
+    /// - chunk_size - the number of items removed per request, up to the 100
+    ///   allowed by Spotify, which is the default
+    async fn playlist_remove_where(
+        &self,
+        playlist_id: PlaylistId<'_>,
+        pred: impl for<'b> Fn(&'b PlaylistItem) -> bool + Send + Sync,
+        chunk_size: Option<usize>,
+    ) -> ClientResult<String> {
+        let chunk_size = validate_chunk_size(chunk_size, 100)?;
+        let limit = self.get_config().pagination_chunks;
+        let playlist = self.playlist(playlist_id.as_ref(), None, None).await?;
+        let mut snapshot_id = playlist.snapshot_id;
+
+        let mut matches = Vec::new();
+        let mut page = playlist.tracks;
+        loop {
+            for (item, position) in page.items.iter().zip(page.offset..) {
+                if !pred(item) {
+                    continue;
+                }
+                if let Some(id) = item.spotify_id() {
+                    matches.push((id.into_static(), position));
+                }
+            }
+
+            if page.next.is_none() {
+                break;
+            }
+            let offset = page.offset + page.items.len() as u32;
+            page = self
+                .playlist_items_manual(playlist_id.as_ref(), None, None, Some(limit), Some(offset))
+                .await?;
+        }
+
+        matches.reverse();
+        for chunk in matches.chunks(chunk_size) {
+            // Grouping the positions by item
+            let mut positions: Vec<(&PlayableId<'_>, Vec<u32>)> = Vec::new();
+            for (id, position) in chunk {
+                let same_item =
+                    |other: &&PlayableId<'_>| other._type() == id._type() && other.id() == id.id();
+                match positions.iter_mut().find(|(other, _)| same_item(other)) {
+                    Some((_, item_positions)) => item_positions.push(*position),
+                    None => positions.push((id, vec![*position])),
+                }
+            }
+            let items = positions.iter().map(|(id, positions)| ItemPositions {
+                id: id.as_ref(),
+                positions,
+            });
+
+            let result = self
+                .playlist_remove_specific_occurrences_of_items(
+                    playlist_id.as_ref(),
+                    items,
+                    Some(&snapshot_id),
+                )
+                .await?;
+            snapshot_id = result.snapshot_id;
+        }
+
+        Ok(snapshot_id)
+    }
+
     /// Add the current authenticated user as a follower of a playlist.
     ///
     /// Parameters:
@@ -481,6 +754,12 @@ pub trait OAuthClient: BaseClient {
     ///
     /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/get-current-users-profile)
     async fn me(&self) -> ClientResult<PrivateUser> {
+        let value = self.me_value().await?;
+        convert_value(value)
+    }
+
+    /// The same as [`Self::me`], but returning the raw JSON response.
+    async fn me_value(&self) -> ClientResult<serde_json::Value> {
         let result = self.endpoint_get("me/", &Query::new()).await?;
         convert_result(&result)
     }
@@ -593,6 +872,37 @@ pub trait OAuthClient: BaseClient {
         convert_result(&result)
     }
 
+    /// Gets all the tracks saved in the current user's library, requesting a
+    /// page of [`Config::pagination_chunks`] tracks at a time until the last
+    /// one. Prefer [`Self::current_user_saved_tracks`] for large libraries if
+    /// the tracks can be processed as they arrive, since this keeps all of
+    /// them in memory.
+    ///
+    /// Parameters:
+    /// - market - Provide this parameter if you want to apply Track Relinking.
+    ///
+    /// [`Config::pagination_chunks`]: crate::Config::pagination_chunks
+    async fn current_user_all_saved_tracks(
+        &self,
+        market: Option<Market>,
+    ) -> ClientResult<Vec<SavedTrack>> {
+        let limit = self.get_config().pagination_chunks;
+        let mut tracks = Vec::new();
+        loop {
+            let offset = tracks.len() as u32;
+            let page = self
+                .current_user_saved_tracks_manual(market, Some(limit), Some(offset))
+                .await?;
+            tracks.reserve(page.total.saturating_sub(offset) as usize);
+            let is_empty = page.items.is_empty();
+            tracks.extend(page.items);
+
+            if is_empty || page.next.is_none() {
+                return Ok(tracks);
+            }
+        }
+    }
+
     /// Gets a list of the artists followed by the current authorized user.
     ///
     /// Parameters:
@@ -616,6 +926,30 @@ pub trait OAuthClient: BaseClient {
         convert_result::<CursorPageFullArtists>(&result).map(|x| x.artists)
     }
 
+    /// Gets all the artists followed by the current authorized user, following
+    /// the cursors of [`Self::current_user_followed_artists`] until the last
+    /// page. A request is performed every [`Config::pagination_chunks`]
+    /// artists.
+    ///
+    /// [`Config::pagination_chunks`]: crate::Config::pagination_chunks
+    async fn current_user_all_followed_artists(&self) -> ClientResult<Vec<FullArtist>> {
+        let limit = self.get_config().pagination_chunks;
+        let mut artists = Vec::new();
+        let mut after = None;
+        loop {
+            let page = self
+                .current_user_followed_artists(after.as_deref(), Some(limit))
+                .await?;
+            let is_empty = page.items.is_empty();
+            artists.extend(page.items);
+
+            after = page.cursors.and_then(|cursors| cursors.after);
+            if is_empty || after.is_none() {
+                return Ok(artists);
+            }
+        }
+    }
+
     /// Remove one or more tracks from the current user's "Your Music" library.
     ///
     /// Parameters:
@@ -1030,20 +1364,17 @@ pub trait OAuthClient: BaseClient {
         offset: Option<Offset>,
         position_ms: Option<time::Duration>,
     ) -> ClientResult<()> {
-        let params = JsonBuilder::new()
-            .required("context_uri", context_uri.uri())
-            .optional(
-                "offset",
-                offset.map(|x| match x {
-                    Offset::Position(position) => json!({ "position": position }),
-                    Offset::Uri(uri) => json!({ "uri": uri }),
-                }),
-            )
-            .optional("position_ms", position_ms)
-            .build();
+        let body = StartPlaybackBody {
+            context_uri: Some(context_uri.uri()),
+            offset,
+            position_ms,
+            ..Default::default()
+        };
+        let params = serde_json::to_value(body)?;
 
         let url = append_device_id("me/player/play", device_id);
-        self.endpoint_put(&url, &params).await?;
+        let result = self.endpoint_put(&url, &params).await;
+        check_active_device(result).await?;
 
         Ok(())
     }
@@ -1064,38 +1395,134 @@ pub trait OAuthClient: BaseClient {
         offset: Option<crate::model::Offset>,
         position_ms: Option<u32>,
     ) -> ClientResult<()> {
-        let params = JsonBuilder::new()
-            .required(
-                "uris",
-                uris.into_iter().map(|id| id.uri()).collect::<Vec<_>>(),
-            )
-            .optional("position_ms", position_ms)
-            .optional(
-                "offset",
-                offset.map(|x| match x {
-                    Offset::Position(position) => json!({ "position": position }),
-                    Offset::Uri(uri) => json!({ "uri": uri }),
-                }),
-            )
-            .build();
+        let body = StartPlaybackBody {
+            uris: Some(uris.into_iter().map(|id| id.uri()).collect()),
+            offset,
+            position_ms: position_ms.map(|ms| time::Duration::from_millis(ms.into())),
+            ..Default::default()
+        };
+        body.validate()?;
+        let params = serde_json::to_value(body)?;
 
         let url = append_device_id("me/player/play", device_id);
-        self.endpoint_put(&url, &params).await?;
+        let result = self.endpoint_put(&url, &params).await;
+        check_active_device(result).await?;
 
         Ok(())
     }
 
+    /// Start playing an episode, resuming it from its saved resume point unless
+    /// it has already been fully played, in which case it starts from the
+    /// beginning.
+    ///
+    /// Note that the resume point is only included in the episode when it was
+    /// fetched with a user token with the `user-read-playback-position` scope.
+    ///
+    /// Parameters:
+    /// - episode - the episode to play
+    /// - device_id - device target for playback
+    ///
+    /// See [`Self::start_uris_playback`] for more information.
+    async fn play_episode_resume(
+        &self,
+        episode: &FullEpisode,
+        device_id: Option<&str>,
+    ) -> ClientResult<()> {
+        let position_ms = if episode.is_finished() {
+            0
+        } else {
+            episode.resume_position().as_millis() as u32
+        };
+
+        let uris = [PlayableId::Episode(episode.id.as_ref())];
+        self.start_uris_playback(uris, device_id, None, Some(position_ms))
+            .await
+    }
+
+    /// Starts playing a recently played track from the album or playlist it
+    /// was played in, so that playback continues with the rest of it. Since
+    /// Spotify only allows starting at a given track in albums and
+    /// playlists, the track is played on its own for other contexts, or when
+    /// it was played without one.
+    ///
+    /// This fails with [`ClientError::InvalidRequest`] for local tracks, which
+    /// can't be played through the API.
+    ///
+    /// Parameters:
+    /// - item - the item of the recently played history to resume from
+    /// - device_id - device target for playback
+    ///
+    /// See [`Self::current_user_recently_played`] for the play history.
+    async fn resume_from_history(
+        &self,
+        item: &PlayHistory,
+        device_id: Option<&str>,
+    ) -> ClientResult<()> {
+        let track_id = item.track.id.as_ref().ok_or_else(|| {
+            ClientError::InvalidRequest("local tracks can't be played".to_owned())
+        })?;
+
+        let context = item.context.as_ref().and_then(Context::play_context_id);
+        match context {
+            Some(context @ (PlayContextId::Album(_) | PlayContextId::Playlist(_))) => {
+                let offset = Offset::Uri(track_id.uri());
+                self.start_context_playback(context, device_id, Some(offset), None)
+                    .await
+            }
+            _ => {
+                let uris = [PlayableId::Track(track_id.as_ref())];
+                self.start_uris_playback(uris, device_id, None, None).await
+            }
+        }
+    }
+
+    /// Takes a snapshot of the current playback, with the item being played,
+    /// its position and the device, so that it can be saved and resumed later
+    /// with [`Self::restore_playback_state`]. Returns `None` if nothing is
+    /// being played.
+    ///
+    /// See [`CurrentPlaybackContext::playback_state`] for more information.
+    async fn capture_playback_state(&self) -> ClientResult<Option<PlaybackState>> {
+        let additional_types = [AdditionalType::Episode];
+        let playback = self.current_playback(None, Some(&additional_types)).await?;
+        Ok(playback.and_then(|playback| playback.playback_state()))
+    }
+
+    /// Starts playing the item of a snapshot taken with
+    /// [`Self::capture_playback_state`] at its saved position, on the same
+    /// device.
+    ///
+    /// This fails with [`ClientError::InvalidId`] if the saved URI isn't a
+    /// track or an episode.
+    async fn restore_playback_state(&self, state: &PlaybackState) -> ClientResult<()> {
+        let uri = state.item_uri.as_str();
+        let item = match parse_uri(uri)? {
+            (Type::Episode, _) => PlayableId::Episode(EpisodeId::from_uri(uri)?),
+            _ => PlayableId::Track(TrackId::from_uri(uri)?),
+        };
+        let position_ms = state.position.as_millis() as u32;
+
+        self.start_uris_playback([item], state.device_id.as_deref(), None, Some(position_ms))
+            .await
+    }
+
     /// Pause a User’s Playback.
     ///
     /// Parameters:
     /// - device_id - device target for playback
     ///
+    /// Spotify responds with `403 Forbidden` when the playback is already
+    /// paused. If [`Config::idempotent_playback`] is enabled, that error is
+    /// ignored as long as the current playback is indeed paused.
+    ///
+    /// [`Config::idempotent_playback`]: crate::Config::idempotent_playback
+    ///
     /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/pause-a-users-playback)
     async fn pause_playback(&self, device_id: Option<&str>) -> ClientResult<()> {
         let url = append_device_id("me/player/pause", device_id);
-        self.endpoint_put(&url, &json!({})).await?;
-
-        Ok(())
+        let result = self.endpoint_put(&url, &json!({})).await;
+        let result = check_active_device(result).await;
+        self.check_playback_restriction(result, false).await
     }
 
     /// Resume a User’s Playback.
@@ -1104,6 +1531,12 @@ pub trait OAuthClient: BaseClient {
     /// - device_id - device target for playback
     /// - position_ms
     ///
+    /// Spotify responds with `403 Forbidden` when the playback is already
+    /// playing. If [`Config::idempotent_playback`] is enabled, that error is
+    /// ignored as long as the current playback is indeed playing.
+    ///
+    /// [`Config::idempotent_playback`]: crate::Config::idempotent_playback
+    ///
     /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/start-a-users-playback)
     async fn resume_playback(
         &self,
@@ -1115,9 +1548,45 @@ pub trait OAuthClient: BaseClient {
             .build();
 
         let url = append_device_id("me/player/play", device_id);
-        self.endpoint_put(&url, &params).await?;
+        let result = self.endpoint_put(&url, &params).await;
+        let result = check_active_device(result).await;
+        self.check_playback_restriction(result, true).await
+    }
 
-        Ok(())
+    /// Turns the `403 Forbidden` returned when pausing or resuming into a
+    /// success if [`Config::idempotent_playback`] is enabled, the error's
+    /// reason is that the playback is already paused or playing, and the
+    /// current playback is indeed in the requested state.
+    ///
+    /// Otherwise, the original error is returned. Since the response can only
+    /// be read once, a `403` is returned as [`ClientError::Api`] when enabled,
+    /// with the status, message and reason from Spotify.
+    ///
+    /// [`Config::idempotent_playback`]: crate::Config::idempotent_playback
+    #[doc(hidden)]
+    async fn check_playback_restriction(
+        &self,
+        result: ClientResult<String>,
+        is_playing: bool,
+    ) -> ClientResult<()> {
+        match result {
+            Err(ClientError::Http(err))
+                if self.get_config().idempotent_playback && err.status_code() == Some(403) =>
+            {
+                let body = err.into_response_body().await.unwrap_or_default();
+                let (error, restricted) = playback_restriction_error(&body, is_playing);
+                if !restricted {
+                    return Err(ClientError::Api(error));
+                }
+
+                // The original error is kept if the state can't be checked
+                match self.current_playback(None, None::<&[_]>).await {
+                    Ok(Some(playback)) if playback.is_playing == is_playing => Ok(()),
+                    _ => Err(ClientError::Api(error)),
+                }
+            }
+            result => result.map(|_| ()),
+        }
     }
 
     /// Skip User’s Playback To Next Track.
@@ -1128,7 +1597,8 @@ pub trait OAuthClient: BaseClient {
     /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/skip-users-playback-to-next-track)
     async fn next_track(&self, device_id: Option<&str>) -> ClientResult<()> {
         let url = append_device_id("me/player/next", device_id);
-        self.endpoint_post(&url, &json!({})).await?;
+        let result = self.endpoint_post(&url, &json!({})).await;
+        check_active_device(result).await?;
 
         Ok(())
     }
@@ -1141,7 +1611,8 @@ pub trait OAuthClient: BaseClient {
     /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/skip-users-playback-to-previous-track)
     async fn previous_track(&self, device_id: Option<&str>) -> ClientResult<()> {
         let url = append_device_id("me/player/previous", device_id);
-        self.endpoint_post(&url, &json!({})).await?;
+        let result = self.endpoint_post(&url, &json!({})).await;
+        check_active_device(result).await?;
 
         Ok(())
     }
@@ -1158,7 +1629,8 @@ pub trait OAuthClient: BaseClient {
             &format!("me/player/seek?position_ms={position_ms}"),
             device_id,
         );
-        self.endpoint_put(&url, &json!({})).await?;
+        let result = self.endpoint_put(&url, &json!({})).await;
+        check_active_device(result).await?;
 
         Ok(())
     }
@@ -1175,28 +1647,45 @@ pub trait OAuthClient: BaseClient {
             &format!("me/player/repeat?state={}", <&str>::from(state)),
             device_id,
         );
-        self.endpoint_put(&url, &json!({})).await?;
+        let result = self.endpoint_put(&url, &json!({})).await;
+        check_active_device(result).await?;
 
         Ok(())
     }
 
     /// Set Volume For User’s Playback.
     ///
+    /// When a device is given and [`Config::check_volume_support`] is
+    /// enabled, the user's devices are requested first, so that
+    /// [`ClientError::VolumeNotSupported`] is returned if the device doesn't
+    /// support setting its volume (see [`Device::can_set_volume`]).
+    ///
     /// Parameters:
-    /// - volume_percent - volume between 0 and 100
+    /// - volume_percent - volume between 0 and 100. Larger values are clamped
+    ///   to 100.
     /// - device_id - device target for playback
     ///
+    /// [`Config::check_volume_support`]: crate::Config::check_volume_support
+    ///
     /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/set-volume-for-users-playback)
     async fn volume(&self, volume_percent: u8, device_id: Option<&str>) -> ClientResult<()> {
-        debug_assert!(
-            volume_percent <= 100u8,
-            "volume must be between 0 and 100, inclusive"
-        );
+        let volume_percent = volume_percent.min(100);
+        if let Some(device_id) = device_id.filter(|_| self.get_config().check_volume_support) {
+            let devices = self.device().await?;
+            let device = devices
+                .iter()
+                .find(|device| device.id.as_deref() == Some(device_id));
+            if matches!(device, Some(device) if !device.can_set_volume()) {
+                return Err(ClientError::VolumeNotSupported);
+            }
+        }
+
         let url = append_device_id(
             &format!("me/player/volume?volume_percent={volume_percent}"),
             device_id,
         );
-        self.endpoint_put(&url, &json!({})).await?;
+        let result = self.endpoint_put(&url, &json!({})).await;
+        check_active_device(result).await?;
 
         Ok(())
     }
@@ -1210,11 +1699,59 @@ pub trait OAuthClient: BaseClient {
     /// [Reference](https://developer.spotify.com/documentation/web-api/reference/#/operations/toggle-shuffle-for-users-playback)
     async fn shuffle(&self, state: bool, device_id: Option<&str>) -> ClientResult<()> {
         let url = append_device_id(&format!("me/player/shuffle?state={state}"), device_id);
-        self.endpoint_put(&url, &json!({})).await?;
+        let result = self.endpoint_put(&url, &json!({})).await;
+        check_active_device(result).await?;
 
         Ok(())
     }
 
+    /// Returns the market of the current user, i.e., their country, which can
+    /// be used as the default market for other requests. It's only requested
+    /// once per access token, and then cached until the token changes.
+    ///
+    /// The country requires the `user-read-private` scope; without it,
+    /// [`Market::FromToken`] is returned instead.
+    async fn user_market(&self) -> ClientResult<Market> {
+        let cache = self.get_user_market_cache();
+        let access_token = self
+            .get_token()
+            .lock()
+            .await
+            .unwrap()
+            .as_ref()
+            .map(|token| token.access_token.clone());
+
+        if let Some((cached_token, market)) = cache.lock().await.unwrap().as_ref() {
+            if Some(cached_token) == access_token.as_ref() {
+                return Ok(*market);
+            }
+        }
+
+        let market = self
+            .me()
+            .await?
+            .country
+            .map_or(Market::FromToken, Market::Country);
+        // The token may have been refreshed by the request
+        let access_token = self
+            .get_token()
+            .lock()
+            .await
+            .unwrap()
+            .as_ref()
+            .map(|token| token.access_token.clone());
+        *cache.lock().await.unwrap() = access_token.map(|token| (token, market));
+
+        Ok(market)
+    }
+
+    /// Returns a handle to control the playback of the given device without
+    /// passing its ID to every method, or of the active device if it's
+    /// `None`.
+    fn player(&self, device_id: Option<&str>) -> Player<'_, Self> {
+        Player::new(self, device_id)
+    }
+
     /// Add an item to the end of the user's playback queue.
     ///
     /// Parameters:
@@ -1230,7 +1767,8 @@ pub trait OAuthClient: BaseClient {
         device_id: Option<&str>,
     ) -> ClientResult<()> {
         let url = append_device_id(&format!("me/player/queue?uri={}", item.uri()), device_id);
-        self.endpoint_post(&url, &json!({})).await?;
+        let result = self.endpoint_post(&url, &json!({})).await;
+        check_active_device(result).await?;
 
         Ok(())
     }
@@ -1,28 +1,117 @@
 //! Synchronous implementation of automatic pagination requests.
 
-use crate::{model::Page, ClientError, ClientResult};
+use crate::{clients::validate_chunk_size, model::Page, ClientError, ClientResult};
+
+use std::ops::Range;
 
 /// Alias for `Iterator<Item = T>`, since sync mode is enabled.
 pub type Paginator<'a, T> = Box<dyn Iterator<Item = T> + 'a>;
 
 /// This is used to handle paginated requests automatically.
-pub fn paginate<'a, T: 'a, Request: 'a>(
+pub fn paginate<'a, T, Request>(req: Request, page_size: u32) -> Paginator<'a, ClientResult<T>>
+where
+    T: 'a,
+    Request: 'a + Fn(u32, u32) -> ClientResult<Page<T>>,
+{
+    paginate_with_progress(req, page_size, |_, _| {})
+}
+
+/// Same as [`paginate`], but `progress` is called after each page is received
+/// with the number of items fetched so far and the `total` of the page. This
+/// can be used with the manually paginated methods of the clients, such as
+/// `playlist_items_manual`.
+///
+/// Only offset-based pages are supported, since cursor-based ones don't have
+/// a reliable `total`. The automatically paginated methods of the clients
+/// don't report their progress.
+pub fn paginate_with_progress<'a, T, Request, Progress>(
     req: Request,
     page_size: u32,
+    mut progress: Progress,
 ) -> Paginator<'a, ClientResult<T>>
 where
-    Request: Fn(u32, u32) -> ClientResult<Page<T>>,
+    T: 'a,
+    Request: 'a + Fn(u32, u32) -> ClientResult<Page<T>>,
+    Progress: 'a + FnMut(u32, u32),
 {
+    let mut fetched = 0;
     let pages = PageIterator {
         req,
         offset: 0,
         done: false,
         page_size,
-    };
+    }
+    .inspect(move |result| {
+        if let Ok(page) = result {
+            fetched += page.items.len() as u32;
+            progress(fetched, page.total);
+        }
+    });
 
     Box::new(pages.flat_map(|result| ResultIter::new(result.map(|page| page.items.into_iter()))))
 }
 
+/// This is used to handle paginated requests automatically by following the
+/// `next` URL of each page, starting with an already fetched one.
+pub fn paginate_next<'a, T, Request>(first: Page<T>, req: Request) -> Paginator<'a, ClientResult<T>>
+where
+    T: 'a,
+    Request: 'a + Fn(String) -> ClientResult<Page<T>>,
+{
+    let mut first = Some(first);
+    let mut next = None;
+    // The next page is only requested once the items of the previous one have
+    // been consumed, since `flat_map` is lazy.
+    let pages = std::iter::from_fn(move || {
+        let page = match first.take() {
+            Some(page) => Ok(page),
+            None => req(next.take()?),
+        };
+        if let Ok(page) = &page {
+            next = page.next.clone();
+        }
+        Some(page)
+    });
+
+    Box::new(pages.flat_map(|result| ResultIter::new(result.map(|page| page.items.into_iter()))))
+}
+
+/// This is used to request the items of a list of length `len` in chunks of
+/// `chunk_size` (`max` by default), yielding each item with its index in the
+/// list as soon as its chunk is received. A failed chunk yields its error, and
+/// the next chunks are still requested.
+///
+/// The chunks are requested one after another, so `concurrency` is only
+/// taken for compatibility with the async version.
+pub fn paginate_chunks<'a, T, Request>(
+    len: usize,
+    chunk_size: Option<usize>,
+    max: usize,
+    _concurrency: usize,
+    req: Request,
+) -> Paginator<'a, ClientResult<(usize, T)>>
+where
+    T: 'a,
+    Request: 'a + Fn(Range<usize>) -> ClientResult<Vec<T>>,
+{
+    let chunk_size = match validate_chunk_size(chunk_size, max) {
+        Ok(chunk_size) => chunk_size,
+        Err(err) => return Box::new(std::iter::once(Err(err))),
+    };
+    let chunks = (0..len).step_by(chunk_size).map(move |start| {
+        let end = len.min(start + chunk_size);
+        let items = req(start..end).map(|items| {
+            items
+                .into_iter()
+                .enumerate()
+                .map(move |(i, item)| (start + i, item))
+        });
+        ResultIter::new(items)
+    });
+
+    Box::new(chunks.flatten())
+}
+
 /// Iterator that repeatedly calls a function that returns a page until an empty
 /// page is returned.
 struct PageIterator<Request> {
@@ -7,6 +7,15 @@
 //! * A `Paginator` struct which wraps the iterable of items
 //! * A `paginate` function, which returns a `Paginator` based on a request that
 //!   may be repeated in order to return a continuous sequence of `Page`s
+//! * A `paginate_with_progress` function, which works like `paginate` but also
+//!   reports the number of fetched items and the `total` of the pages after
+//!   each request, e.g., for a progress bar. It's meant to be used with the
+//!   manually paginated methods, since the `Paginator` itself can't expose the
+//!   `total` before its first page is requested
+//! * A `paginate_next` function, which returns a `Paginator` that starts with
+//!   an already fetched `Page` and then requests the `next` URL of each page
+//! * A `paginate_chunks` function, which returns a `Paginator` over the items
+//!   of a list that has to be requested in chunks, along with their index
 //!
 //! Note that `Paginator` should actually be a trait so that a dynamic
 //! allocation can be avoided when returning it with `-> impl Iterator<T>`, as
@@ -25,6 +34,6 @@ mod iter;
 mod stream;
 
 #[cfg(feature = "__sync")]
-pub use iter::{paginate, Paginator};
+pub use iter::{paginate, paginate_chunks, paginate_next, paginate_with_progress, Paginator};
 #[cfg(feature = "__async")]
-pub use stream::{paginate, Paginator};
+pub use stream::{paginate, paginate_chunks, paginate_next, paginate_with_progress, Paginator};
@@ -1,10 +1,13 @@
 //! Asynchronous implementation of automatic pagination requests.
 
-use crate::{model::Page, ClientResult};
+use crate::{clients::validate_chunk_size, model::Page, ClientResult};
 
-use std::pin::Pin;
+use std::{ops::Range, pin::Pin};
 
-use futures::{future::Future, stream::Stream};
+use futures::{
+    future::Future,
+    stream::{self, Stream, StreamExt},
+};
 
 /// Alias for `futures::stream::Stream<Item = T>`, since async mode is enabled.
 pub type Paginator<'a, T> = Pin<Box<dyn Stream<Item = T> + 'a>>;
@@ -15,6 +18,28 @@ where
     T: 'a + Unpin,
     Fut: Future<Output = ClientResult<Page<T>>>,
     Request: 'a + Fn(u32, u32) -> Fut,
+{
+    paginate_with_progress(req, page_size, |_, _| {})
+}
+
+/// Same as [`paginate`], but `progress` is called after each page is received
+/// with the number of items fetched so far and the `total` of the page. This
+/// can be used with the manually paginated methods of the clients, such as
+/// `playlist_items_manual`.
+///
+/// Only offset-based pages are supported, since cursor-based ones don't have
+/// a reliable `total`. The automatically paginated methods of the clients
+/// don't report their progress.
+pub fn paginate_with_progress<'a, T, Fut, Request, Progress>(
+    req: Request,
+    page_size: u32,
+    mut progress: Progress,
+) -> Paginator<'a, ClientResult<T>>
+where
+    T: 'a + Unpin,
+    Fut: Future<Output = ClientResult<Page<T>>>,
+    Request: 'a + Fn(u32, u32) -> Fut,
+    Progress: 'a + FnMut(u32, u32),
 {
     use async_stream::stream;
     let mut offset = 0;
@@ -22,6 +47,7 @@ where
         loop {
             let page = req(page_size, offset).await?;
             offset += page.items.len() as u32;
+            progress(offset, page.total);
             for item in page.items {
                 yield Ok(item);
             }
@@ -31,3 +57,74 @@ where
         }
     })
 }
+
+/// This is used to handle paginated requests automatically by following the
+/// `next` URL of each page, starting with an already fetched one.
+pub fn paginate_next<'a, T, Fut, Request>(
+    first: Page<T>,
+    req: Request,
+) -> Paginator<'a, ClientResult<T>>
+where
+    T: 'a + Unpin,
+    Fut: Future<Output = ClientResult<Page<T>>>,
+    Request: 'a + Fn(String) -> Fut,
+{
+    use async_stream::stream;
+    Box::pin(stream! {
+        let mut page = first;
+        loop {
+            let next = page.next.take();
+            for item in page.items {
+                yield Ok(item);
+            }
+            match next {
+                Some(url) => page = req(url).await?,
+                None => break,
+            }
+        }
+    })
+}
+
+/// This is used to request the items of a list of length `len` in chunks of
+/// `chunk_size` (`max` by default), yielding each item with its index in the
+/// list as soon as its chunk is received. Up to `concurrency` chunks are
+/// requested at the same time, but the items are still yielded in order. A
+/// failed chunk yields its error, and the next chunks are still requested.
+pub fn paginate_chunks<'a, T, Fut, Request>(
+    len: usize,
+    chunk_size: Option<usize>,
+    max: usize,
+    concurrency: usize,
+    req: Request,
+) -> Paginator<'a, ClientResult<(usize, T)>>
+where
+    T: 'a + Unpin,
+    Fut: 'a + Future<Output = ClientResult<Vec<T>>>,
+    Request: 'a + Fn(Range<usize>) -> Fut,
+{
+    let chunk_size = match validate_chunk_size(chunk_size, max) {
+        Ok(chunk_size) => chunk_size,
+        Err(err) => return Box::pin(stream::once(async { Err(err) })),
+    };
+    let chunks = (0..len).step_by(chunk_size).map(move |start| {
+        let end = len.min(start + chunk_size);
+        let chunk = req(start..end);
+        async move { (start, chunk.await) }
+    });
+
+    Box::pin(
+        stream::iter(chunks)
+            .buffered(concurrency.max(1))
+            .flat_map(|(start, result)| {
+                let items = match result {
+                    Ok(items) => items
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, item)| Ok((start + i, item)))
+                        .collect(),
+                    Err(err) => vec![Err(err)],
+                };
+                stream::iter(items)
+            }),
+    )
+}
@@ -0,0 +1,137 @@
+use crate::{
+    clients::OAuthClient,
+    model::{AdditionalType, CurrentPlaybackContext, RepeatState},
+    ClientResult,
+};
+
+use maybe_async::maybe_async;
+
+/// A handle to control the playback of a single device, so that its ID
+/// doesn't have to be passed to every playback endpoint. It's obtained with
+/// [`OAuthClient::player`].
+///
+/// If no device ID is given, the requests target the user's currently active
+/// device, and fail with [`ClientError::NoActiveDevice`] if there's none, like
+/// the rest of the playback endpoints.
+///
+/// [`ClientError::NoActiveDevice`]: crate::ClientError::NoActiveDevice
+#[derive(Clone, Debug)]
+pub struct Player<'a, C: OAuthClient> {
+    client: &'a C,
+    device_id: Option<String>,
+}
+
+#[maybe_async]
+impl<'a, C: OAuthClient> Player<'a, C> {
+    pub(crate) fn new(client: &'a C, device_id: Option<&str>) -> Self {
+        Player {
+            client,
+            device_id: device_id.map(ToOwned::to_owned),
+        }
+    }
+
+    /// The device controlled by this player, or `None` for the active one.
+    #[must_use]
+    pub fn device_id(&self) -> Option<&str> {
+        self.device_id.as_deref()
+    }
+
+    /// Resumes the playback. See [`OAuthClient::resume_playback`].
+    pub async fn play(&self) -> ClientResult<()> {
+        self.client.resume_playback(self.device_id(), None).await
+    }
+
+    /// Pauses the playback. See [`OAuthClient::pause_playback`].
+    pub async fn pause(&self) -> ClientResult<()> {
+        self.client.pause_playback(self.device_id()).await
+    }
+
+    /// Skips to the next track. See [`OAuthClient::next_track`].
+    pub async fn next(&self) -> ClientResult<()> {
+        self.client.next_track(self.device_id()).await
+    }
+
+    /// Skips to the previous track. See [`OAuthClient::previous_track`].
+    pub async fn previous(&self) -> ClientResult<()> {
+        self.client.previous_track(self.device_id()).await
+    }
+
+    /// Seeks to the given position in the current track. See
+    /// [`OAuthClient::seek_track`].
+    pub async fn seek(&self, position_ms: u32) -> ClientResult<()> {
+        self.client.seek_track(position_ms, self.device_id()).await
+    }
+
+    /// Sets the volume, between 0 and 100. See [`OAuthClient::volume`].
+    pub async fn volume(&self, volume_percent: u8) -> ClientResult<()> {
+        self.client.volume(volume_percent, self.device_id()).await
+    }
+
+    /// Toggles shuffle. See [`OAuthClient::shuffle`].
+    pub async fn shuffle(&self, state: bool) -> ClientResult<()> {
+        self.client.shuffle(state, self.device_id()).await
+    }
+
+    /// Sets the repeat mode. See [`OAuthClient::repeat`].
+    pub async fn repeat(&self, state: RepeatState) -> ClientResult<()> {
+        self.client.repeat(state, self.device_id()).await
+    }
+
+    /// The user's current playback, including episodes. See
+    /// [`OAuthClient::current_playback`].
+    ///
+    /// Note that Spotify is eventually consistent, so the effect of a command
+    /// sent right before may not be visible yet.
+    pub async fn playback(&self) -> ClientResult<Option<CurrentPlaybackContext>> {
+        let additional_types = [AdditionalType::Episode];
+        self.client
+            .current_playback(None, Some(&additional_types))
+            .await
+    }
+
+    // The following methods send a command and then fetch the playback, which
+    // saves a separate call for UIs that always refresh after a command. As
+    // explained in `playback`, the state they return may lag behind.
+
+    /// Like [`Self::play`], returning the playback afterwards.
+    pub async fn play_and_fetch(&self) -> ClientResult<Option<CurrentPlaybackContext>> {
+        self.play().await?;
+        self.playback().await
+    }
+
+    /// Like [`Self::pause`], returning the playback afterwards.
+    pub async fn pause_and_fetch(&self) -> ClientResult<Option<CurrentPlaybackContext>> {
+        self.pause().await?;
+        self.playback().await
+    }
+
+    /// Like [`Self::next`], returning the playback afterwards.
+    pub async fn next_and_fetch(&self) -> ClientResult<Option<CurrentPlaybackContext>> {
+        self.next().await?;
+        self.playback().await
+    }
+
+    /// Like [`Self::previous`], returning the playback afterwards.
+    pub async fn previous_and_fetch(&self) -> ClientResult<Option<CurrentPlaybackContext>> {
+        self.previous().await?;
+        self.playback().await
+    }
+
+    /// Like [`Self::seek`], returning the playback afterwards.
+    pub async fn seek_and_fetch(
+        &self,
+        position_ms: u32,
+    ) -> ClientResult<Option<CurrentPlaybackContext>> {
+        self.seek(position_ms).await?;
+        self.playback().await
+    }
+
+    /// Like [`Self::volume`], returning the playback afterwards.
+    pub async fn volume_and_fetch(
+        &self,
+        volume_percent: u8,
+    ) -> ClientResult<Option<CurrentPlaybackContext>> {
+        self.volume(volume_percent).await?;
+        self.playback().await
+    }
+}
@@ -125,6 +125,7 @@ mod auth_code;
 mod auth_code_pkce;
 mod client_creds;
 pub mod clients;
+mod rate_limit;
 pub mod sync;
 mod util;
 
@@ -133,18 +134,21 @@ pub use rspotify_http as http;
 pub use rspotify_macros as macros;
 pub use rspotify_model as model;
 // Top-level re-exports
-pub use auth_code::AuthCodeSpotify;
+pub use auth_code::{AuthCodeSpotify, DeviceAuthHandle};
 pub use auth_code_pkce::AuthCodePkceSpotify;
 pub use client_creds::ClientCredsSpotify;
 pub use macros::scopes;
 pub use model::Token;
+pub use rate_limit::RateLimiter;
 
 use crate::{http::HttpError, model::Id};
 
 use std::{
     collections::{HashMap, HashSet},
     env,
+    ops::Range,
     path::PathBuf,
+    time::Duration,
 };
 
 use getrandom::getrandom;
@@ -153,6 +157,9 @@ use thiserror::Error;
 pub mod prelude {
     pub use crate::clients::{BaseClient, OAuthClient};
     pub use crate::model::idtypes::{Id, PlayContextId, PlayableId};
+    pub use crate::model::{
+        WithArtists, WithFollowers, WithLanguages, WithRestrictions, WithResumePoint,
+    };
 }
 
 /// Common headers as constants.
@@ -190,7 +197,11 @@ pub(in crate) mod auth_urls {
 }
 
 /// Possible errors returned from the `rspotify` client.
+///
+/// New variants may be added in minor releases, so matching on it requires a
+/// wildcard arm.
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum ClientError {
     #[error("json parse error: {0}")]
     ParseJson(#[from] serde_json::Error),
@@ -216,6 +227,52 @@ pub enum ClientError {
 
     #[error("model error: {0}")]
     Model(#[from] model::ModelError),
+
+    #[error("the `from_token` market requires a token authorized by a user")]
+    FromTokenWithoutUser,
+
+    #[error("the token doesn't have the required scope `{0}`")]
+    MissingScope(model::Scope),
+
+    #[error("id parse error: {0}")]
+    InvalidId(#[from] model::IdError),
+
+    #[error("the chunk size must be between 1 and {max}, but it's {size}")]
+    InvalidChunkSize { size: usize, max: usize },
+
+    #[error("at most {max} ids can be sent in a single request, but {count} were given")]
+    TooManyIds { count: usize, max: usize },
+
+    #[error("there is no active device to control the playback")]
+    NoActiveDevice,
+
+    #[error("api error: {0}")]
+    Api(model::ApiError),
+
+    #[error("the track or episode doesn't have a preview")]
+    MissingPreview,
+
+    #[error("invalid request: {0}")]
+    InvalidRequest(String),
+
+    #[error("the device doesn't support setting its volume")]
+    VolumeNotSupported,
+
+    #[error("the state of the authorization response doesn't match the one sent")]
+    StateMismatch,
+
+    #[error("the authorization was denied: {0}")]
+    AuthorizationDenied(String),
+
+    #[error("the authorization wasn't completed in time")]
+    AuthTimeout,
+
+    #[error("playlist {playlist_id} was created with {added} tracks, but then failed: {source}")]
+    PlaylistNotFilled {
+        playlist_id: model::PlaylistId<'static>,
+        added: usize,
+        source: Box<ClientError>,
+    },
 }
 
 // The conversion has to be done manually because it's in a `Box<T>`
@@ -227,9 +284,31 @@ impl From<HttpError> for ClientError {
 
 pub type ClientResult<T> = Result<T, ClientError>;
 
+/// The result of a bulk request that is split into chunks, where a failed
+/// chunk doesn't stop the rest from being requested.
+#[derive(Debug)]
+pub struct BulkResult<T> {
+    /// One entry per requested item, in the same order. It's `None` if the
+    /// item wasn't found, or if its chunk failed.
+    pub ok: Vec<Option<T>>,
+    /// The indices of the items in each failed chunk, with its error.
+    pub errors: Vec<(Range<usize>, ClientError)>,
+}
+
+impl<T> BulkResult<T> {
+    /// Whether all the chunks were requested successfully.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
 pub const DEFAULT_API_PREFIX: &str = "https://api.spotify.com/v1/";
 pub const DEFAULT_CACHE_PATH: &str = ".spotify_token_cache.json";
 pub const DEFAULT_PAGINATION_CHUNKS: u32 = 50;
+pub const DEFAULT_STREAM_CONCURRENCY: usize = 4;
+pub const DEFAULT_RETRY_BACKOFF_BASE: Duration = Duration::from_millis(500);
+pub const DEFAULT_RETRY_BACKOFF_CAP: Duration = Duration::from_secs(30);
 
 /// Struct to configure the Spotify client.
 #[derive(Debug, Clone)]
@@ -250,6 +329,16 @@ pub struct Config {
     /// request, which most times is 50.
     pub pagination_chunks: u32,
 
+    /// The maximum number of chunks requested at the same time by the
+    /// streaming methods, like
+    /// [`tracks_features_stream`](crate::clients::BaseClient::tracks_features_stream).
+    /// The items are still yielded in order. By default this is
+    /// [`DEFAULT_STREAM_CONCURRENCY`].
+    ///
+    /// This is ignored in sync mode, where the chunks are always requested
+    /// one after another.
+    pub stream_concurrency: usize,
+
     /// Whether or not to save the authentication token into a JSON file,
     /// then reread the token from JSON file when launching the program without
     /// following the full auth process again
@@ -258,6 +347,91 @@ pub struct Config {
     /// Whether or not to check if the token has expired when sending a
     /// request with credentials, and in that case, automatically refresh it.
     pub token_refreshing: bool,
+
+    /// Whether or not to refresh the token and retry the request once when
+    /// Spotify rejects it with `401 Unauthorized`, e.g., if the token expired
+    /// while the request was being sent. This requires a refresh token in the
+    /// authorization code flows.
+    ///
+    /// By default this is false, so that the error is returned instead.
+    pub token_refresh_on_unauthorized: bool,
+
+    /// Whether or not pausing an already paused playback, or resuming one
+    /// that is already playing, should succeed. Spotify rejects these
+    /// requests with `403 Forbidden` ("Restriction violated"); when this is
+    /// enabled, the current playback is fetched after such an error and the
+    /// request is considered successful if it's already in the requested
+    /// state. Other `403` errors, like the ones for a missing scope or
+    /// without Spotify Premium, are still returned, but as
+    /// [`ClientError::Api`] with the status, message and reason from Spotify,
+    /// since their body has to be read first.
+    ///
+    /// By default this is false, so that the error is returned as is.
+    pub idempotent_playback: bool,
+
+    /// Whether or not [`OAuthClient::volume`] should request the user's
+    /// devices first when a device is given, so that it fails with
+    /// [`ClientError::VolumeNotSupported`] instead of sending the request to a
+    /// device that doesn't support setting its volume. This costs an extra
+    /// request for every call.
+    ///
+    /// By default this is false, so that the request is sent directly.
+    ///
+    /// [`OAuthClient::volume`]: crate::clients::OAuthClient::volume
+    pub check_volume_support: bool,
+
+    /// The maximum number of times a `GET` request is retried when Spotify
+    /// responds with a server error (5xx status code), which may happen
+    /// temporarily during maintenance. Non-idempotent requests (`POST`, `PUT`
+    /// and `DELETE`) are never retried so that they aren't applied twice.
+    ///
+    /// By default this is zero, i.e., retrying is disabled.
+    pub max_retries: u32,
+
+    /// The delay before the first retry, which is doubled after every attempt
+    /// (with some random jitter). By default this is
+    /// [`DEFAULT_RETRY_BACKOFF_BASE`].
+    pub retry_backoff_base: Duration,
+
+    /// The maximum delay between retries. By default this is
+    /// [`DEFAULT_RETRY_BACKOFF_CAP`].
+    pub retry_backoff_cap: Duration,
+
+    /// An optional client-side limit on the number of requests per second.
+    /// Requests that would exceed it are delayed until there's enough budget
+    /// left, instead of waiting for Spotify to reject them with `429 Too Many
+    /// Requests`. The limiter is shared among all the clients that use a clone
+    /// of it.
+    ///
+    /// By default this is `None`, so requests aren't throttled.
+    pub rate_limiter: Option<RateLimiter>,
+
+    /// The language range sent in the `Accept-Language` header of the
+    /// requests to the endpoints, e.g., `es-ES`, so that the names of the
+    /// categories and playlists are localized even if the endpoint doesn't
+    /// take a `locale` parameter. It can also be set with
+    /// [`Config::with_accept_language`].
+    ///
+    /// By default this is `None`, so that Spotify responds in English.
+    pub accept_language: Option<LanguageRange>,
+
+    /// Whether or not the HTTP client should log the requests and their
+    /// responses, with their secrets redacted. They're logged under the
+    /// `rspotify_http` target, with the `debug` level for the method, URL and
+    /// status code, and with `trace` for the bodies.
+    ///
+    /// By default this is true.
+    pub log_requests: bool,
+}
+
+impl Config {
+    /// Sets [`Config::accept_language`], failing with
+    /// [`ClientError::InvalidRequest`] if `tag` isn't a language range like
+    /// `fr`, `pt-BR` or `*`.
+    pub fn with_accept_language(mut self, tag: &str) -> ClientResult<Self> {
+        self.accept_language = Some(LanguageRange::new(tag)?);
+        Ok(self)
+    }
 }
 
 impl Default for Config {
@@ -266,12 +440,63 @@ impl Default for Config {
             prefix: String::from(DEFAULT_API_PREFIX),
             cache_path: PathBuf::from(DEFAULT_CACHE_PATH),
             pagination_chunks: DEFAULT_PAGINATION_CHUNKS,
+            stream_concurrency: DEFAULT_STREAM_CONCURRENCY,
             token_cached: false,
             token_refreshing: false,
+            token_refresh_on_unauthorized: false,
+            idempotent_playback: false,
+            check_volume_support: false,
+            max_retries: 0,
+            retry_backoff_base: DEFAULT_RETRY_BACKOFF_BASE,
+            retry_backoff_cap: DEFAULT_RETRY_BACKOFF_CAP,
+            rate_limiter: None,
+            accept_language: None,
+            log_requests: true,
+        }
+    }
+}
+
+/// A language range for the `Accept-Language` header, which can only be
+/// built from a valid one with [`LanguageRange::new`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LanguageRange(String);
+
+impl LanguageRange {
+    /// Fails with [`ClientError::InvalidRequest`] if `tag` isn't a language
+    /// range like `fr`, `pt-BR` or `*`.
+    pub fn new(tag: &str) -> ClientResult<Self> {
+        if !is_language_range(tag) {
+            return Err(ClientError::InvalidRequest(format!(
+                "invalid language range: {tag:?}"
+            )));
         }
+
+        Ok(Self(tag.to_owned()))
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
     }
 }
 
+/// Whether the input is a basic language range as defined in [RFC
+/// 4647](https://datatracker.ietf.org/doc/html/rfc4647#section-2.1): a
+/// primary subtag of 1 to 8 letters, optionally followed by subtags of 1 to 8
+/// letters or digits separated by hyphens, or just `*`.
+fn is_language_range(tag: &str) -> bool {
+    if tag == "*" {
+        return true;
+    }
+
+    let mut subtags = tag.split('-');
+    let is_subtag = |subtag: &str, valid: fn(&u8) -> bool| {
+        (1..=8).contains(&subtag.len()) && subtag.as_bytes().iter().all(valid)
+    };
+    matches!(subtags.next(), Some(primary) if is_subtag(primary, u8::is_ascii_alphabetic))
+        && subtags.all(|subtag| is_subtag(subtag, u8::is_ascii_alphanumeric))
+}
+
 /// Generate `length` random chars from the Operating System.
 ///
 /// It is assumed that system always provides high-quality cryptographically
@@ -286,8 +511,24 @@ pub(in crate) fn generate_random_string(length: usize, alphabet: &[u8]) -> Strin
         .collect()
 }
 
+/// Joins the bare IDs with commas, e.g., `id1,id2`, which is the format
+/// expected by the endpoints that take multiple IDs in the query. Both owned
+/// and borrowed IDs are accepted.
+///
+/// ```
+/// use rspotify::{join_ids, model::TrackId};
+///
+/// let ids = [
+///     TrackId::from_uri("spotify:track:4iV5W9uYEdYUVa79Axb7Rh").unwrap(),
+///     TrackId::from_id("1301WleyT98MSxVHPZCA6M").unwrap(),
+/// ];
+/// assert_eq!(
+///     join_ids(&ids),
+///     "4iV5W9uYEdYUVa79Axb7Rh,1301WleyT98MSxVHPZCA6M"
+/// );
+/// ```
 #[inline]
-pub(in crate) fn join_ids<'a, T: Id + 'a>(ids: impl IntoIterator<Item = T>) -> String {
+pub fn join_ids<T: Id>(ids: impl IntoIterator<Item = T>) -> String {
     let ids = ids.into_iter().collect::<Vec<_>>();
     ids.iter().map(Id::id).collect::<Vec<_>>().join(",")
 }
@@ -400,13 +641,45 @@ impl OAuth {
             ..Default::default()
         })
     }
+
+    /// The requested scopes that haven't been granted for the given token,
+    /// e.g., because new ones were added since it was cached.
+    #[must_use]
+    pub fn missing_scopes<'a>(&'a self, token: &Token) -> HashSet<&'a str> {
+        self.scopes
+            .iter()
+            .filter(|scope| !token.scopes.contains(*scope))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Whether the user has to authorize the app again to obtain a token with
+    /// all the requested scopes, since the given one lacks some of them. This
+    /// is best checked at startup with the cached token, instead of finding
+    /// out later with a `403 Forbidden`.
+    #[must_use]
+    pub fn needs_reauth(&self, token: &Token) -> bool {
+        !self.scopes.is_subset(&token.scopes)
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::{alphabets, generate_random_string, Credentials};
+    use crate::{
+        alphabets, generate_random_string, is_language_range, scopes, Credentials, OAuth, Token,
+    };
     use std::collections::HashSet;
 
+    #[test]
+    fn test_is_language_range() {
+        for tag in ["es", "pt-BR", "zh-Hant-TW", "de-CH-1996", "*"] {
+            assert!(is_language_range(tag), "{tag}");
+        }
+        for tag in ["", "es_ES", "es-", "-ES", "castilian-ES", "1a", "es;q=0.9"] {
+            assert!(!is_language_range(tag), "{tag}");
+        }
+    }
+
     #[test]
     fn test_generate_random_string() {
         let mut containers = HashSet::new();
@@ -431,4 +704,29 @@ mod test {
             Some(&"Basic cmFtc2F5OjEyMzQ1Ng==".to_owned())
         );
     }
+
+    #[test]
+    fn test_needs_reauth() {
+        let token = Token {
+            scopes: scopes!("user-read-private", "streaming"),
+            ..Default::default()
+        };
+
+        let oauth = OAuth {
+            scopes: scopes!("streaming"),
+            ..Default::default()
+        };
+        assert!(!oauth.needs_reauth(&token));
+        assert!(oauth.missing_scopes(&token).is_empty());
+
+        let oauth = OAuth {
+            scopes: scopes!("streaming", "user-library-read"),
+            ..Default::default()
+        };
+        assert!(oauth.needs_reauth(&token));
+        assert_eq!(
+            oauth.missing_scopes(&token),
+            ["user-library-read"].iter().copied().collect()
+        );
+    }
 }
@@ -0,0 +1,115 @@
+//! Client-side rate limiting, so that batch jobs don't get throttled by
+//! Spotify.
+
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// A token bucket that delays requests so that they stay below a target rate.
+///
+/// It's configured with [`Config::rate_limiter`](crate::Config::rate_limiter).
+/// Its state is reference-counted, so clones of the limiter (and thus of the
+/// clients using it) share the same budget. Up to `requests_per_second`
+/// requests may be sent in a burst, after which they are spread evenly.
+#[derive(Clone)]
+pub struct RateLimiter {
+    requests_per_second: u32,
+    bucket: Arc<Mutex<Bucket>>,
+}
+
+struct Bucket {
+    /// The available tokens, which is negative when requests are queued.
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    /// Creates a rate limiter with a full bucket.
+    ///
+    /// # Panics
+    ///
+    /// If `requests_per_second` is zero.
+    pub fn new(requests_per_second: u32) -> Self {
+        assert!(
+            requests_per_second > 0,
+            "the rate limit must be at least one request per second"
+        );
+
+        RateLimiter {
+            requests_per_second,
+            bucket: Arc::new(Mutex::new(Bucket {
+                tokens: f64::from(requests_per_second),
+                last_refill: Instant::now(),
+            })),
+        }
+    }
+
+    /// The configured maximum number of requests per second.
+    pub fn requests_per_second(&self) -> u32 {
+        self.requests_per_second
+    }
+
+    /// Takes a token from the bucket and returns how long the request has to
+    /// wait before it can be sent.
+    pub(crate) fn reserve(&self) -> Duration {
+        self.reserve_at(Instant::now())
+    }
+
+    fn reserve_at(&self, now: Instant) -> Duration {
+        let rate = f64::from(self.requests_per_second);
+        let mut bucket = self.bucket.lock().unwrap();
+
+        let elapsed = now.saturating_duration_since(bucket.last_refill);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * rate).min(rate);
+        bucket.last_refill = now;
+
+        bucket.tokens -= 1.0;
+        if bucket.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / rate)
+        }
+    }
+}
+
+impl fmt::Debug for RateLimiter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RateLimiter")
+            .field("requests_per_second", &self.requests_per_second)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_burst_then_throttle() {
+        let limiter = RateLimiter::new(2);
+        let now = Instant::now();
+
+        assert_eq!(limiter.reserve_at(now), Duration::ZERO);
+        assert_eq!(limiter.reserve_at(now), Duration::ZERO);
+        assert_eq!(limiter.reserve_at(now), Duration::from_millis(500));
+        assert_eq!(limiter.reserve_at(now), Duration::from_secs(1));
+
+        // Waiting refills the bucket, but never above its capacity
+        let later = now + Duration::from_secs(10);
+        assert_eq!(limiter.reserve_at(later), Duration::ZERO);
+        assert_eq!(limiter.reserve_at(later), Duration::ZERO);
+        assert_eq!(limiter.reserve_at(later), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_shared_between_clones() {
+        let limiter = RateLimiter::new(1);
+        let clone = limiter.clone();
+        let now = Instant::now();
+
+        assert_eq!(limiter.reserve_at(now), Duration::ZERO);
+        assert_eq!(clone.reserve_at(now), Duration::from_secs(1));
+    }
+}
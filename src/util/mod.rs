@@ -1,7 +1,8 @@
 //! General internal utilities used across this crate.
 
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Duration};
 
+use getrandom::getrandom;
 use serde::Serialize;
 use std::marker::PhantomData;
 
@@ -90,3 +91,48 @@ impl Natural for Zero {
 impl<T: Natural> Natural for Successor<T> {
     const VALUE: usize = T::VALUE + 1;
 }
+
+/// Computes the delay before the given retry attempt (starting at zero) with
+/// an exponential backoff. The result is never larger than `cap`, and up to
+/// half of it is randomly subtracted so that concurrent clients don't retry
+/// all at once.
+pub fn retry_backoff(base: Duration, cap: Duration, attempt: u32) -> Duration {
+    let factor = 2u32.checked_pow(attempt).unwrap_or(u32::MAX);
+    let delay = base.saturating_mul(factor).min(cap);
+
+    let mut buf = [0u8; 4];
+    getrandom(&mut buf).unwrap();
+    let jitter = f64::from(u32::from_le_bytes(buf)) / f64::from(u32::MAX) / 2.0;
+    delay.mul_f64(1.0 - jitter)
+}
+
+/// Blocks the current task for the given duration. The async version requires
+/// the `tokio` runtime.
+#[cfg(feature = "__async")]
+pub async fn sleep(duration: Duration) {
+    tokio::time::sleep(duration).await;
+}
+
+/// Blocks the current thread for the given duration.
+#[cfg(feature = "__sync")]
+pub fn sleep(duration: Duration) {
+    std::thread::sleep(duration);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_retry_backoff() {
+        let base = Duration::from_millis(100);
+        let cap = Duration::from_secs(1);
+
+        for attempt in 0..40 {
+            let expected = base.saturating_mul(1 << attempt.min(31)).min(cap);
+            let delay = retry_backoff(base, cap, attempt);
+            assert!(delay <= expected);
+            assert!(delay >= expected / 2);
+        }
+    }
+}
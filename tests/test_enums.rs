@@ -30,6 +30,17 @@ fn test_date_precision() {
     assert_eq!(<&str>::from(month), "month");
 }
 
+#[test]
+fn test_date_precision_unknown() {
+    let day: DatePrecision = serde_json::from_str(r#""day""#).unwrap();
+    assert_eq!(day, DatePrecision::Day);
+
+    let unknown: DatePrecision = serde_json::from_str(r#""decade""#).unwrap();
+    assert_eq!(unknown, DatePrecision::Unknown("decade".to_owned()));
+    assert_eq!(unknown.as_str(), "decade");
+    assert_eq!(serde_json::to_string(&unknown).unwrap(), r#""decade""#);
+}
+
 #[test]
 fn test_album_type_convert_from_str() {
     let appears_on = AlbumType::AppearsOn;
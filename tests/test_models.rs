@@ -1,7 +1,7 @@
 use chrono::{DateTime, NaiveDateTime, Utc};
 use rspotify::model::*;
 use serde::de::DeserializeOwned;
-use std::time::Duration;
+use std::{collections::HashMap, time::Duration};
 
 #[track_caller]
 fn deserialize<T>(data: impl AsRef<str>) -> T
@@ -55,6 +55,170 @@ fn test_simplified_track() {
     assert_eq!(track.duration, duration);
 }
 
+#[test]
+fn test_simplified_track_artists() {
+    let json_str = r#"
+{
+    "artists": [ {
+      "external_urls": {
+        "spotify": "https://open.spotify.com/artist/08td7MxkoHQkXnWAYD8d6Q"
+      },
+      "href": "https://api.spotify.com/v1/artists/08td7MxkoHQkXnWAYD8d6Q",
+      "id": "08td7MxkoHQkXnWAYD8d6Q",
+      "name": "Tania Bowra",
+      "type": "artist",
+      "uri": "spotify:artist:08td7MxkoHQkXnWAYD8d6Q"
+    } ],
+    "available_markets": ["US"],
+    "disc_number": 1,
+    "duration_ms": 276773,
+    "explicit": false,
+    "external_urls": {
+      "spotify": "https://open.spotify.com/track/2TpxZ7JUBn3uw46aR7qd6V"
+    },
+    "href": "https://api.spotify.com/v1/tracks/2TpxZ7JUBn3uw46aR7qd6V",
+    "id": "2TpxZ7JUBn3uw46aR7qd6V",
+    "name": "All I Want",
+    "preview_url": "https://p.scdn.co/mp3-preview/6d00206e32194d15df329d4770e4fa1f2ced3f57",
+    "track_number": 1,
+    "type": "track",
+    "uri": "spotify:track:2TpxZ7JUBn3uw46aR7qd6V",
+    "is_local": false
+  }
+
+"#;
+    let mut track: SimplifiedTrack = deserialize(json_str);
+    assert_eq!(track.artist_names(), "Tania Bowra");
+    assert_eq!(
+        track.artist_ids(),
+        [ArtistId::from_id("08td7MxkoHQkXnWAYD8d6Q").unwrap()]
+    );
+
+    // Local tracks may have artists without an ID
+    track.artists.push(SimplifiedArtist {
+        name: "Local Artist".to_owned(),
+        ..Default::default()
+    });
+    assert_eq!(track.artist_names(), "Tania Bowra & Local Artist");
+    assert_eq!(track.artist_ids().len(), 1);
+}
+
+#[test]
+fn test_simplified_track_available_markets() {
+    let spain = Market::Country(Country::Spain);
+    let japan = Market::Country(Country::Japan);
+
+    let mut track = SimplifiedTrack {
+        available_markets: Some(vec!["US".to_owned(), "ES".to_owned()]),
+        ..Default::default()
+    };
+    assert_eq!(track.is_available_in(spain), Some(true));
+    assert_eq!(track.is_available_in(japan), Some(false));
+    assert_eq!(track.is_available_in(Market::FromToken), None);
+    assert_eq!(
+        track.unavailable_markets(&[spain, japan, Market::FromToken]),
+        vec![japan]
+    );
+    assert!(track.is_available_in_all(&[spain, Market::FromToken]));
+    assert!(!track.is_available_in_all(&[spain, japan]));
+
+    // Unknown availability
+    track.available_markets = None;
+    assert_eq!(track.is_available_in(spain), None);
+    assert!(track.unavailable_markets(&[spain, japan]).is_empty());
+    assert!(track.is_available_in_all(&[spain, japan]));
+}
+
+#[test]
+fn test_simplified_track_restrictions() {
+    let json_str = r#"
+{
+    "artists": [],
+    "disc_number": 1,
+    "duration_ms": 276773,
+    "explicit": false,
+    "external_urls": {
+      "spotify": "https://open.spotify.com/track/2TpxZ7JUBn3uw46aR7qd6V"
+    },
+    "href": "https://api.spotify.com/v1/tracks/2TpxZ7JUBn3uw46aR7qd6V",
+    "id": "2TpxZ7JUBn3uw46aR7qd6V",
+    "is_playable": true,
+    "restrictions": {
+      "reason": "payment_required"
+    },
+    "name": "All I Want",
+    "preview_url": null,
+    "track_number": 1,
+    "type": "track",
+    "uri": "spotify:track:2TpxZ7JUBn3uw46aR7qd6V",
+    "is_local": false
+  }
+"#;
+    let track: SimplifiedTrack = deserialize(json_str);
+    assert!(!track.can_play());
+    assert_eq!(
+        track.restriction_reason(),
+        Some(&RestrictionReason::Unknown("payment_required".to_owned()))
+    );
+
+    let track = SimplifiedTrack {
+        restrictions: Some(Restriction {
+            reason: RestrictionReason::Market,
+        }),
+        ..Default::default()
+    };
+    assert!(!track.can_play());
+    assert_eq!(
+        serde_json::to_value(track.restrictions).unwrap(),
+        serde_json::json!({ "reason": "market" })
+    );
+    assert!(SimplifiedTrack::default().can_play());
+}
+
+#[test]
+fn test_null_followers() {
+    let json_str = r#"
+        {
+            "external_urls": {
+                "spotify": "https://open.spotify.com/artist/0OdUWJ0sBjDrqHygGUXeCF"
+            },
+            "followers": null,
+            "genres": [],
+            "href": "https://api.spotify.com/v1/artists/0OdUWJ0sBjDrqHygGUXeCF",
+            "id": "0OdUWJ0sBjDrqHygGUXeCF",
+            "images": [],
+            "name": "Band of Horses",
+            "popularity": 65,
+            "type": "artist",
+            "uri": "spotify:artist:0OdUWJ0sBjDrqHygGUXeCF"
+        }
+        "#;
+    let full_artist: FullArtist = deserialize(json_str);
+    assert_eq!(full_artist.followers, None);
+    assert_eq!(full_artist.total_followers(), 0);
+
+    let json_str = r#"
+        {
+            "display_name": "Ronald Pompa",
+            "external_urls": {
+                "spotify": "https://open.spotify.com/user/wizzler"
+            },
+            "followers": {
+                "href": null,
+                "total": null
+            },
+            "href": "https://api.spotify.com/v1/users/wizzler",
+            "id": "wizzler",
+            "images": [],
+            "type": "user",
+            "uri": "spotify:user:wizzler"
+        }
+        "#;
+    let user: PublicUser = deserialize(json_str);
+    assert_eq!(user.followers, Some(Followers { total: 0 }));
+    assert_eq!(user.total_followers(), 0);
+}
+
 #[test]
 fn test_public_user() {
     let json_str = r#"
@@ -119,6 +283,44 @@ fn test_private_user() {
     assert_eq!(private_user.country.unwrap(), Country::UnitedStates);
 }
 
+#[test]
+fn test_private_user_explicit_allowed() {
+    let json_str = r#"
+        {
+            "country": "US",
+            "display_name": "Sergey",
+            "email": "vixatew967@top-email.org",
+            "explicit_content": {
+              "filter_enabled": false,
+              "filter_locked": false
+            },
+            "external_urls": {
+              "spotify": "https://open.spotify.com/user/waq5aexykhm6nlv0cnwdieng0"
+            },
+            "followers": {
+              "href": null,
+              "total": 0
+            },
+            "href": "https://api.spotify.com/v1/users/waq5aexykhm6nlv0cnwdieng0",
+            "id": "waq5aexykhm6nlv0cnwdieng0",
+            "images": [],
+            "product": "open",
+            "type": "user",
+            "uri": "spotify:user:waq5aexykhm6nlv0cnwdieng0"
+          } 
+        "#;
+    let mut private_user: PrivateUser = deserialize(json_str);
+    assert!(private_user.explicit_allowed());
+
+    private_user.explicit_content = Some(ExplicitContent {
+        filter_enabled: true,
+        filter_locked: true,
+    });
+    assert!(!private_user.explicit_allowed());
+    private_user.explicit_content = None;
+    assert!(private_user.explicit_allowed());
+}
+
 #[test]
 fn test_full_artist() {
     let json_str = r#"
@@ -150,7 +352,63 @@ fn test_full_artist() {
         "#;
     let full_artist: FullArtist = deserialize(json_str);
     assert_eq!(full_artist.name, "Band of Horses");
-    assert_eq!(full_artist.followers.total, 833247);
+    assert_eq!(full_artist.total_followers(), 833247);
+}
+
+#[test]
+fn test_full_artist_into_simplified() {
+    let json_str = r#"
+        {
+            "external_urls": {
+                "spotify": "https://open.spotify.com/artist/0OdUWJ0sBjDrqHygGUXeCF"
+            },
+            "followers": {
+                "href": null,
+                "total": 833247
+            },
+            "genres": [
+                "indie folk"
+            ],
+            "href": "https://api.spotify.com/v1/artists/0OdUWJ0sBjDrqHygGUXeCF",
+            "id": "0OdUWJ0sBjDrqHygGUXeCF",
+            "images": [
+                {
+                    "height": 640,
+                    "url": "https://i.scdn.co/image/0f9a5013134de288af7d49a962417f4200539b47",
+                    "width": 640
+                }
+            ],
+            "name": "Band of Horses",
+            "popularity": 65,
+            "type": "artist",
+            "uri": "spotify:artist:0OdUWJ0sBjDrqHygGUXeCF"
+        }
+        "#;
+    let full_artist: FullArtist = deserialize(json_str);
+    let simplified = SimplifiedArtist::from(full_artist.clone());
+    assert_eq!(simplified.id.as_ref(), Some(&full_artist.id));
+    assert_eq!(simplified.name, full_artist.name);
+}
+
+#[test]
+fn test_simplified_artist() {
+    let json_str = r#"
+        {
+            "external_urls": {
+                "spotify": "https://open.spotify.com/artist/0OdUWJ0sBjDrqHygGUXeCF"
+            },
+            "href": "https://api.spotify.com/v1/artists/0OdUWJ0sBjDrqHygGUXeCF",
+            "id": "0OdUWJ0sBjDrqHygGUXeCF",
+            "name": "Band of Horses",
+            "type": "artist",
+            "uri": "spotify:artist:0OdUWJ0sBjDrqHygGUXeCF"
+        }
+        "#;
+    let simplified_artist: SimplifiedArtist = deserialize(json_str);
+    assert_eq!(simplified_artist.name, "Band of Horses");
+
+    // The simplified object lacks fields such as `genres` or `followers`
+    assert!(serde_json::from_str::<FullArtist>(json_str).is_err());
 }
 
 #[test]
@@ -199,6 +457,52 @@ fn test_simplified_episode() {
     assert_eq!(simplified_episode.duration, duration);
 }
 
+#[test]
+fn test_simplified_episode_languages() {
+    let json_str = r#"
+        {
+            "audio_preview_url": "https://p.scdn.co/mp3-preview/d8b916e1872de2bb0285d8c7bfe2b4b57011c85c",
+            "description": "En unik barockträdgård från 1600-talet gömmer sig på Södermalm i Stockholm och nu gräver arkeologerna ut parken och kvarteret där Bellman lekte som barn.  Nu grävs Carl Michael Bellmans kvarter fram på Södermalm i Stockholm. Under dagens jordyta döljer sig en rik barockträdgård, men också tunga industrier från en tid då Söder var stockholmarnas sommarnöje. Dessutom om hur arkeologer ska kunna bli bättre att hitta de fattigas kulturarv. För vid sidan av slott, borgar och hallar finns torpen och backstugorna som utgör ett fortfarande okänt kulturarv som angår oss alla. Programledare Tobias Svanelid.",
+            "duration_ms": 2685023,
+            "explicit": false,
+            "external_urls": {
+                "spotify": "https://open.spotify.com/episode/3brfPv3PaUhspkm1T9ZVl8"
+            },
+            "href": "https://api.spotify.com/v1/episodes/3brfPv3PaUhspkm1T9ZVl8",
+            "id": "3brfPv3PaUhspkm1T9ZVl8",
+            "images": [
+                {
+                "height": 640,
+                "url": "https://i.scdn.co/image/65497c8c1bef1b783d2be6a1c73b294d953f9406",
+                "width": 640
+                }
+            ],
+            "is_externally_hosted": false,
+            "is_playable": true,
+            "language": "sv",
+            "languages": [
+                "sv"
+            ],
+            "name": "På Bellmans bakgata",
+            "release_date": "2020-10-20",
+            "release_date_precision": "day",
+            "resume_point": {
+                "fully_played": false,
+                "resume_position_ms": 0
+            },
+            "type": "episode",
+            "uri": "spotify:episode:3brfPv3PaUhspkm1T9ZVl8"
+        }
+        "#;
+    let mut simplified_episode: SimplifiedEpisode = deserialize(json_str);
+    simplified_episode.languages = vec!["en-US".to_owned(), "ES".to_owned()];
+    assert_eq!(simplified_episode.primary_language().as_deref(), Some("en"));
+    assert_eq!(simplified_episode.languages_normalized(), ["en-us", "es"]);
+
+    simplified_episode.languages.clear();
+    assert_eq!(simplified_episode.primary_language(), None);
+}
+
 #[test]
 fn test_full_episode() {
     let json_str = r#"
@@ -265,49 +569,237 @@ fn test_full_episode() {
     assert_eq!(full_episode.release_date_precision, DatePrecision::Day);
     let duration = Duration::from_millis(1502795);
     assert_eq!(full_episode.duration, duration);
-}
-
-#[test]
-fn test_copyright() {
-    let json_str = r#"
-	[ {
-	    "text" : "(P) 2000 Sony Music Entertainment Inc.",
-	    "type" : "P"
-	} ]
-
-"#;
-    let copyrights: Vec<Copyright> = deserialize(json_str);
-    assert_eq!(copyrights[0]._type, CopyrightType::Performance);
-}
 
-#[test]
-fn test_audio_analysis_section() {
-    let json_str = r#"
-        {
-            "start": 237.02356,
-            "duration": 18.32542,
-            "confidence": 1,
-            "loudness": -20.074,
-            "tempo": 98.253,
-            "tempo_confidence": 0.767,
-            "key": 5,
-            "key_confidence": 0.327,
-            "mode": 1,
-            "mode_confidence": 0.566,
-            "time_signature": 4,
-            "time_signature_confidence": 1
-        }
-        "#;
-    let session: AudioAnalysisSection = deserialize(json_str);
-    // Comparison of floating point numbers
-    assert!((session.time_interval.duration - 18.32542).abs() < f32::EPSILON);
+    // The `type` field decides the variant of a playable item
+    let item: PlayableItem = deserialize(json_str);
+    assert_eq!(item, PlayableItem::Episode(full_episode.clone()));
+    let serialized = serde_json::to_string(&item).unwrap();
+    assert_eq!(deserialize::<PlayableItem>(serialized), item);
 }
 
 #[test]
-fn test_audio_analysis_segments() {
+fn test_full_episode_into_simplified() {
     let json_str = r#"
-         {
-            "start": 252.15601,
+    {
+        "audio_preview_url": "https://p.scdn.co/mp3-preview/566fcc94708f39bcddc09e4ce84a8e5db8f07d4d",
+        "description": "En ny tysk ",
+        "duration_ms": 1502795,
+        "explicit": false,
+        "external_urls": {
+            "spotify": "https://open.spotify.com/episode/512ojhOuo1ktJprKbVcKyQ"
+        },
+        "href": "https://api.spotify.com/v1/episodes/512ojhOuo1ktJprKbVcKyQ",
+        "id": "512ojhOuo1ktJprKbVcKyQ",
+        "images": [
+            {
+                "height": 64,
+                "url": "https://i.scdn.co/image/e29c75799cad73927fad713011edad574868d8da",
+                "width": 64
+            }
+        ],
+        "is_externally_hosted": false,
+        "is_playable": true,
+        "language": "sv",
+        "languages": [
+            "sv"
+        ],
+        "name": "Tredje rikets knarkande granskas",
+        "release_date": "2015-10-01",
+        "release_date_precision": "day",
+        "show": {
+            "available_markets": [
+                "ZA"
+            ],
+            "copyrights": [],
+            "description": "Vi är där historien är. Ansvarig utgivare: Nina Glans",
+            "explicit": false,
+            "external_urls": {
+                "spotify": "https://open.spotify.com/show/38bS44xjbVVZ3No3ByF1dJ"
+            },
+            "href": "https://api.spotify.com/v1/shows/38bS44xjbVVZ3No3ByF1dJ",
+            "id": "38bS44xjbVVZ3No3ByF1dJ",
+            "images": [
+                {
+                    "height": 64,
+                    "url": "https://i.scdn.co/image/3dc007829bc0663c24089e46743a9f4ae15e65f8",
+                    "width": 64
+                }
+            ],
+            "is_externally_hosted": false,
+            "languages": [
+                "sv"
+            ],
+            "media_type": "audio",
+            "name": "Vetenskapsradion Historia",
+            "publisher": "Sveriges Radio",
+            "type": "show",
+            "uri": "spotify:show:38bS44xjbVVZ3No3ByF1dJ"
+        },
+        "type": "episode",
+        "uri": "spotify:episode:512ojhOuo1ktJprKbVcKyQ"
+    }
+        "#;
+    let full_episode: FullEpisode = deserialize(json_str);
+    let simplified = SimplifiedEpisode::from(full_episode.clone());
+    assert_eq!(simplified.id, full_episode.id);
+    assert_eq!(simplified.resume_point, full_episode.resume_point);
+}
+
+#[test]
+fn test_full_episode_omitted_fields() {
+    // Requested with a market, so there are no `available_markets`, and
+    // without `is_playable`, which means that it can be played
+    let json_str = r#"
+    {
+        "audio_preview_url": null,
+        "description": "En ny tysk ",
+        "duration_ms": 1502795,
+        "explicit": false,
+        "external_urls": {
+            "spotify": "https://open.spotify.com/episode/512ojhOuo1ktJprKbVcKyQ"
+        },
+        "href": "https://api.spotify.com/v1/episodes/512ojhOuo1ktJprKbVcKyQ",
+        "id": "512ojhOuo1ktJprKbVcKyQ",
+        "images": [],
+        "is_externally_hosted": false,
+        "language": "sv",
+        "languages": ["sv"],
+        "name": "Tredje rikets knarkande granskas",
+        "release_date": "2015-10-01",
+        "release_date_precision": "day",
+        "show": {
+            "copyrights": [],
+            "description": "Vi är där historien är. Ansvarig utgivare: Nina Glans",
+            "explicit": false,
+            "external_urls": {
+                "spotify": "https://open.spotify.com/show/38bS44xjbVVZ3No3ByF1dJ"
+            },
+            "href": "https://api.spotify.com/v1/shows/38bS44xjbVVZ3No3ByF1dJ",
+            "id": "38bS44xjbVVZ3No3ByF1dJ",
+            "images": [],
+            "is_externally_hosted": false,
+            "languages": ["sv"],
+            "media_type": "audio",
+            "name": "Vetenskapsradion Historia",
+            "publisher": "Sveriges Radio",
+            "type": "show",
+            "uri": "spotify:show:38bS44xjbVVZ3No3ByF1dJ"
+        },
+        "type": "episode",
+        "uri": "spotify:episode:512ojhOuo1ktJprKbVcKyQ"
+    }
+    "#;
+    let full_episode: FullEpisode = deserialize(json_str);
+    assert!(full_episode.is_playable);
+    assert!(full_episode.can_play());
+    assert!(full_episode.show.available_markets.is_empty());
+}
+
+#[test]
+fn test_episode_resume_point() {
+    let json_str = r#"
+    {
+        "audio_preview_url": null,
+        "description": "En ny tysk ",
+        "duration_ms": 1502795,
+        "explicit": false,
+        "external_urls": {
+            "spotify": "https://open.spotify.com/episode/512ojhOuo1ktJprKbVcKyQ"
+        },
+        "href": "https://api.spotify.com/v1/episodes/512ojhOuo1ktJprKbVcKyQ",
+        "id": "512ojhOuo1ktJprKbVcKyQ",
+        "images": [],
+        "is_externally_hosted": false,
+        "language": "sv",
+        "languages": ["sv"],
+        "name": "Tredje rikets knarkande granskas",
+        "release_date": "2015-10-01",
+        "release_date_precision": "day",
+        "resume_point": {
+            "fully_played": true,
+            "resume_position_ms": 90000
+        },
+        "show": {
+            "copyrights": [],
+            "description": "Vi är där historien är. Ansvarig utgivare: Nina Glans",
+            "explicit": false,
+            "external_urls": {
+                "spotify": "https://open.spotify.com/show/38bS44xjbVVZ3No3ByF1dJ"
+            },
+            "href": "https://api.spotify.com/v1/shows/38bS44xjbVVZ3No3ByF1dJ",
+            "id": "38bS44xjbVVZ3No3ByF1dJ",
+            "images": [],
+            "is_externally_hosted": false,
+            "languages": ["sv"],
+            "media_type": "audio",
+            "name": "Vetenskapsradion Historia",
+            "publisher": "Sveriges Radio",
+            "type": "show",
+            "uri": "spotify:show:38bS44xjbVVZ3No3ByF1dJ"
+        },
+        "type": "episode",
+        "uri": "spotify:episode:512ojhOuo1ktJprKbVcKyQ"
+    }
+    "#;
+    let mut full_episode: FullEpisode = deserialize(json_str);
+    assert_eq!(full_episode.resume_position(), Duration::from_secs(90));
+    assert!(full_episode.is_finished());
+
+    // Without the `user-read-playback-position` scope
+    full_episode.resume_point = None;
+    assert_eq!(full_episode.resume_position(), Duration::ZERO);
+    assert!(!full_episode.is_finished());
+
+    let simplified_episode = SimplifiedEpisode::from(full_episode);
+    assert_eq!(simplified_episode.resume_position(), Duration::ZERO);
+    assert!(!simplified_episode.is_finished());
+    assert_eq!(
+        simplified_episode.resume_point_or_default(),
+        &ResumePoint::NOT_STARTED
+    );
+}
+
+#[test]
+fn test_copyright() {
+    let json_str = r#"
+	[ {
+	    "text" : "(P) 2000 Sony Music Entertainment Inc.",
+	    "type" : "P"
+	} ]
+
+"#;
+    let copyrights: Vec<Copyright> = deserialize(json_str);
+    assert_eq!(copyrights[0]._type, CopyrightType::Performance);
+}
+
+#[test]
+fn test_audio_analysis_section() {
+    let json_str = r#"
+        {
+            "start": 237.02356,
+            "duration": 18.32542,
+            "confidence": 1,
+            "loudness": -20.074,
+            "tempo": 98.253,
+            "tempo_confidence": 0.767,
+            "key": 5,
+            "key_confidence": 0.327,
+            "mode": 1,
+            "mode_confidence": 0.566,
+            "time_signature": 4,
+            "time_signature_confidence": 1
+        }
+        "#;
+    let session: AudioAnalysisSection = deserialize(json_str);
+    // Comparison of floating point numbers
+    assert!((session.time_interval.duration - 18.32542).abs() < f32::EPSILON);
+}
+
+#[test]
+fn test_audio_analysis_segments() {
+    let json_str = r#"
+         {
+            "start": 252.15601,
             "duration": 3.19297,
             "confidence": 0.522,
             "loudness_start": -23.356,
@@ -340,6 +832,34 @@ fn test_actions() {
     assert_eq!(actions.disallows[0], DisallowKey::Resuming);
 }
 
+#[test]
+fn test_actions_allowed() {
+    let json_str = r#"
+        {
+            "disallows": {
+                "resuming": true
+            }
+        }
+        "#;
+    let actions: Actions = deserialize(json_str);
+    assert!(!actions.can_resume());
+    assert!(actions.can_pause());
+    assert!(actions.can_skip_next());
+    assert!(actions.is_allowed(DisallowKey::TransferringPlayback));
+
+    let json_str = r#"
+        {
+            "disallows": {
+                "skipping_next": true,
+                "seeking": false
+            }
+        }
+        "#;
+    let actions: Actions = deserialize(json_str);
+    assert!(!actions.can_skip_next());
+    assert!(actions.can_seek());
+}
+
 #[test]
 fn test_recommendations_seed() {
     let json_str = r#"
@@ -356,6 +876,56 @@ fn test_recommendations_seed() {
     assert_eq!(seed._type, RecommendationsSeedType::Artist);
 }
 
+#[test]
+fn test_recommendations() {
+    let json_str = r#"
+        {
+            "seeds": [
+                {
+                    "initialPoolSize": 500,
+                    "afterFilteringSize": 380,
+                    "afterRelinkingSize": 365,
+                    "href": "https://api.spotify.com/v1/artists/4NHQUGzhtTLFvgF5SZesLK",
+                    "id": "4NHQUGzhtTLFvgF5SZesLK",
+                    "type": "ARTIST"
+                },
+                {
+                    "initialPoolSize": 250,
+                    "afterFilteringSize": 172,
+                    "afterRelinkingSize": 172,
+                    "href": "https://api.spotify.com/v1/tracks/0c6xIDDpzE81m2q797ordA",
+                    "id": "0c6xIDDpzE81m2q797ordA",
+                    "type": "TRACK"
+                },
+                {
+                    "initialPoolSize": 0,
+                    "afterFilteringSize": 0,
+                    "afterRelinkingSize": 0,
+                    "href": null,
+                    "id": "classical",
+                    "type": "GENRE"
+                }
+            ],
+            "tracks": []
+        }
+        "#;
+    let recommendations: Recommendations = deserialize(json_str);
+    assert_eq!(recommendations.seeds.len(), 3);
+
+    let track_seed = &recommendations.seeds[1];
+    assert_eq!(track_seed._type, RecommendationsSeedType::Track);
+    assert_eq!(track_seed.initial_pool_size, 250);
+    assert_eq!(track_seed.after_filtering_size, 172);
+    assert_eq!(track_seed.after_relinking_size, 172);
+
+    // Genre seeds have no link, and may not contribute any tracks
+    let genre_seed = &recommendations.seeds[2];
+    assert_eq!(genre_seed._type, RecommendationsSeedType::Genre);
+    assert_eq!(genre_seed.id, "classical");
+    assert_eq!(genre_seed.href, None);
+    assert_eq!(genre_seed.after_relinking_size, 0);
+}
+
 #[test]
 fn test_full_playlist() {
     let json_str_images = r#"
@@ -490,7 +1060,7 @@ fn test_full_playlist() {
         full_playlist.id.uri(),
         "spotify:playlist:3cEYpjA9oz9GiPac4AsH4n".to_string()
     );
-    assert_eq!(full_playlist.followers.total, 109);
+    assert_eq!(full_playlist.total_followers(), 109);
 }
 
 #[test]
@@ -523,7 +1093,556 @@ fn test_audio_features() {
 }
 
 #[test]
-fn test_full_track() {
+fn test_audio_features_to_vector() {
+    let json = r#"
+    {
+        "duration_ms" : 255349,
+        "key" : 5,
+        "mode" : 0,
+        "time_signature" : 4,
+        "acousticness" : 0.514,
+        "danceability" : 0.735,
+        "energy" : 0.578,
+        "instrumentalness" : 0.0902,
+        "liveness" : 0.159,
+        "loudness" : -11.840,
+        "speechiness" : 0.0461,
+        "valence" : 0.624,
+        "tempo" : 98.002,
+        "id" : "06AKEBrKUckW0KREUWRnvT",
+        "uri" : "spotify:track:06AKEBrKUckW0KREUWRnvT",
+        "track_href" : "https://api.spotify.com/v1/tracks/06AKEBrKUckW0KREUWRnvT",
+        "analysis_url" : "https://api.spotify.com/v1/audio-analysis/06AKEBrKUckW0KREUWRnvT",
+        "type" : "audio_features"
+    }
+    "#;
+    let audio_features: AudioFeatures = deserialize(json);
+    let vector = audio_features.to_vector();
+    let names = AudioFeatures::feature_names();
+    assert_eq!(vector.len(), names.len());
+    assert_eq!(names[0], "danceability");
+    assert_eq!(vector[0], 0.735);
+    assert!((vector[7] - 98.002 / 250.0).abs() < 1e-6);
+    assert!((vector[8] - (1.0 - 11.840 / 60.0)).abs() < 1e-6);
+    assert!(vector.iter().all(|value| (0.0..=1.0).contains(value)));
+}
+
+#[test]
+fn test_audio_features_payload_with_null() {
+    let json = r#"
+    {
+        "audio_features": [
+            {
+                "duration_ms" : 255349,
+                "key" : 5,
+                "mode" : 0,
+                "time_signature" : 4,
+                "acousticness" : 0.514,
+                "danceability" : 0.735,
+                "energy" : 0.578,
+                "instrumentalness" : 0.0902,
+                "liveness" : 0.159,
+                "loudness" : -11.840,
+                "speechiness" : 0.0461,
+                "valence" : 0.624,
+                "tempo" : 98.002,
+                "id" : "06AKEBrKUckW0KREUWRnvT",
+                "uri" : "spotify:track:06AKEBrKUckW0KREUWRnvT",
+                "track_href" : "https://api.spotify.com/v1/tracks/06AKEBrKUckW0KREUWRnvT",
+                "analysis_url" : "https://api.spotify.com/v1/audio-analysis/06AKEBrKUckW0KREUWRnvT",
+                "type" : "audio_features"
+            },
+            null
+        ]
+    }
+    "#;
+    let payload: AudioFeaturesPayload = serde_json::from_str(json).unwrap();
+    assert_eq!(payload.audio_features.len(), 2);
+    assert!(payload.audio_features[0].is_some());
+    assert!(payload.audio_features[1].is_none());
+}
+
+#[test]
+fn test_full_track_omitted_fields() {
+    // A track nested in another object, without the fields that Spotify only
+    // includes in some contexts
+    let json = r#"
+    {
+        "album": {
+            "album_type": "single",
+            "artists": [],
+            "external_urls": {},
+            "href": null,
+            "id": null,
+            "images": [],
+            "name": "Cut To The Feeling",
+            "type": "album",
+            "uri": null
+        },
+        "artists": [],
+        "disc_number": 1,
+        "duration_ms": 207959,
+        "explicit": false,
+        "external_urls": {},
+        "href": null,
+        "id": null,
+        "is_local": true,
+        "name": "Cut To The Feeling",
+        "preview_url": null,
+        "track_number": 1,
+        "type": "track",
+        "uri": "spotify:local:::Cut+To+The+Feeling:207"
+    }
+    "#;
+    let full_track: FullTrack = deserialize(json);
+    assert!(full_track.available_markets.is_empty());
+    assert!(full_track.external_ids.is_empty());
+    assert_eq!(full_track.popularity, 0);
+    assert_eq!(full_track.is_playable, None);
+    assert_eq!(full_track.restrictions, None);
+}
+
+#[test]
+fn test_common_markets() {
+    let json = r#"
+    {
+        "album": {
+            "album_type": "single",
+            "artists": [],
+            "external_urls": {},
+            "href": null,
+            "id": null,
+            "images": [],
+            "name": "Cut To The Feeling",
+            "type": "album",
+            "uri": null
+        },
+        "artists": [],
+        "available_markets": ["ES", "JP", "US", "XX"],
+        "disc_number": 1,
+        "duration_ms": 207959,
+        "explicit": false,
+        "external_urls": {},
+        "href": null,
+        "id": null,
+        "is_local": false,
+        "name": "Cut To The Feeling",
+        "preview_url": null,
+        "track_number": 1,
+        "type": "track",
+        "uri": null
+    }
+    "#;
+    let first: FullTrack = deserialize(json);
+    let mut second = first.clone();
+    second.available_markets = vec!["US".to_owned(), "XX".to_owned(), "ES".to_owned()];
+    let mut unknown = first.clone();
+    unknown.available_markets.clear();
+
+    let spain = Market::Country(Country::Spain);
+    let usa = Market::Country(Country::UnitedStates);
+    let japan = Market::Country(Country::Japan);
+
+    // Unknown country codes and markets are skipped
+    let tracks = [first.clone(), unknown.clone(), second.clone()];
+    assert_eq!(common_markets(&tracks), Some(vec![spain, usa]));
+    assert_eq!(common_markets(&[unknown.clone()]), None);
+    assert_eq!(common_markets(&[]), None);
+
+    assert!(first.is_available_in_all(&[spain, japan, Market::FromToken]));
+    assert!(!second.is_available_in_all(&[spain, japan]));
+    assert!(unknown.is_available_in_all(&[spain, japan]));
+}
+
+#[test]
+fn test_available_markets() {
+    let json = r#"{"markets": ["ES", "US", "XX"]}"#;
+    let markets = serde_json::from_str::<Markets>(json)
+        .unwrap()
+        .markets
+        .into_iter()
+        .map(AvailableMarket::from)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        markets,
+        [
+            AvailableMarket {
+                code: "ES".to_owned(),
+                market: Some(Market::Country(Country::Spain)),
+            },
+            AvailableMarket {
+                code: "US".to_owned(),
+                market: Some(Market::Country(Country::UnitedStates)),
+            },
+            // Unknown codes are kept
+            AvailableMarket {
+                code: "XX".to_owned(),
+                market: None,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_full_track() {
+    let json = r#"
+    {
+  "album": {
+    "album_type": "single",
+    "artists": [
+      {
+        "external_urls": {
+          "spotify": "https://open.spotify.com/artist/6sFIWsNpZYqfjUpaCgueju"
+        },
+        "href": "https://api.spotify.com/v1/artists/6sFIWsNpZYqfjUpaCgueju",
+        "id": "6sFIWsNpZYqfjUpaCgueju",
+        "name": "Carly Rae Jepsen",
+        "type": "artist",
+        "uri": "spotify:artist:6sFIWsNpZYqfjUpaCgueju"
+      }
+    ],
+    "available_markets": [
+      "ZA"
+    ],
+    "external_urls": {
+      "spotify": "https://open.spotify.com/album/0tGPJ0bkWOUmH7MEOR77qc"
+    },
+    "href": "https://api.spotify.com/v1/albums/0tGPJ0bkWOUmH7MEOR77qc",
+    "id": "0tGPJ0bkWOUmH7MEOR77qc",
+    "images": [
+      {
+        "height": 64,
+        "url": "https://i.scdn.co/image/5a73a056d0af707b4119a883d87285feda543fbb",
+        "width": 64
+      }
+    ],
+    "name": "Cut To The Feeling",
+    "release_date": "2017-05-26",
+    "release_date_precision": "day",
+    "type": "album",
+    "uri": "spotify:album:0tGPJ0bkWOUmH7MEOR77qc"
+  },
+  "artists": [
+    {
+      "external_urls": {
+        "spotify": "https://open.spotify.com/artist/6sFIWsNpZYqfjUpaCgueju"
+      },
+      "href": "https://api.spotify.com/v1/artists/6sFIWsNpZYqfjUpaCgueju",
+      "id": "6sFIWsNpZYqfjUpaCgueju",
+      "name": "Carly Rae Jepsen",
+      "type": "artist",
+      "uri": "spotify:artist:6sFIWsNpZYqfjUpaCgueju"
+    }
+  ],
+  "available_markets": [
+    "ZA"
+  ],
+  "disc_number": 1,
+  "duration_ms": 207959,
+  "explicit": false,
+  "external_ids": {
+    "isrc": "USUM71703861"
+  },
+  "external_urls": {
+    "spotify": "https://open.spotify.com/track/11dFghVXANMlKmJXsNCbNl"
+  },
+  "href": "https://api.spotify.com/v1/tracks/11dFghVXANMlKmJXsNCbNl",
+  "id": "11dFghVXANMlKmJXsNCbNl",
+  "is_local": false,
+  "name": "Cut To The Feeling",
+  "popularity": 63,
+  "preview_url": "https://p.scdn.co/mp3-preview/3eb16018c2a700240e9dfb8817b6f2d041f15eb1?cid=774b29d4f13844c495f206cafdad9c86",
+  "track_number": 1,
+  "type": "track",
+  "uri": "spotify:track:11dFghVXANMlKmJXsNCbNl"
+}
+    "#;
+    let full_track: FullTrack = deserialize(json);
+    let duration = Duration::from_millis(207959);
+    assert_eq!(full_track.duration, duration);
+}
+
+#[test]
+fn test_full_track_release_date_precision() {
+    let json = r#"
+    {
+  "album": {
+    "album_type": "single",
+    "artists": [
+      {
+        "external_urls": {
+          "spotify": "https://open.spotify.com/artist/6sFIWsNpZYqfjUpaCgueju"
+        },
+        "href": "https://api.spotify.com/v1/artists/6sFIWsNpZYqfjUpaCgueju",
+        "id": "6sFIWsNpZYqfjUpaCgueju",
+        "name": "Carly Rae Jepsen",
+        "type": "artist",
+        "uri": "spotify:artist:6sFIWsNpZYqfjUpaCgueju"
+      }
+    ],
+    "available_markets": [
+      "ZA"
+    ],
+    "external_urls": {
+      "spotify": "https://open.spotify.com/album/0tGPJ0bkWOUmH7MEOR77qc"
+    },
+    "href": "https://api.spotify.com/v1/albums/0tGPJ0bkWOUmH7MEOR77qc",
+    "id": "0tGPJ0bkWOUmH7MEOR77qc",
+    "images": [
+      {
+        "height": 64,
+        "url": "https://i.scdn.co/image/5a73a056d0af707b4119a883d87285feda543fbb",
+        "width": 64
+      }
+    ],
+    "name": "Cut To The Feeling",
+    "release_date": "2017-05-26",
+    "release_date_precision": "day",
+    "type": "album",
+    "uri": "spotify:album:0tGPJ0bkWOUmH7MEOR77qc"
+  },
+  "artists": [
+    {
+      "external_urls": {
+        "spotify": "https://open.spotify.com/artist/6sFIWsNpZYqfjUpaCgueju"
+      },
+      "href": "https://api.spotify.com/v1/artists/6sFIWsNpZYqfjUpaCgueju",
+      "id": "6sFIWsNpZYqfjUpaCgueju",
+      "name": "Carly Rae Jepsen",
+      "type": "artist",
+      "uri": "spotify:artist:6sFIWsNpZYqfjUpaCgueju"
+    }
+  ],
+  "available_markets": [
+    "ZA"
+  ],
+  "disc_number": 1,
+  "duration_ms": 207959,
+  "explicit": false,
+  "external_ids": {
+    "isrc": "USUM71703861"
+  },
+  "external_urls": {
+    "spotify": "https://open.spotify.com/track/11dFghVXANMlKmJXsNCbNl"
+  },
+  "href": "https://api.spotify.com/v1/tracks/11dFghVXANMlKmJXsNCbNl",
+  "id": "11dFghVXANMlKmJXsNCbNl",
+  "is_local": false,
+  "name": "Cut To The Feeling",
+  "popularity": 63,
+  "preview_url": "https://p.scdn.co/mp3-preview/3eb16018c2a700240e9dfb8817b6f2d041f15eb1?cid=774b29d4f13844c495f206cafdad9c86",
+  "track_number": 1,
+  "type": "track",
+  "uri": "spotify:track:11dFghVXANMlKmJXsNCbNl"
+}
+    "#;
+    let full_track: FullTrack = deserialize(json);
+    assert_eq!(
+        full_track.album.release_date_precision,
+        Some(DatePrecision::Day)
+    );
+}
+
+#[test]
+fn test_full_track_into_simplified() {
+    let json = r#"
+    {
+  "album": {
+    "album_type": "single",
+    "artists": [
+      {
+        "external_urls": {
+          "spotify": "https://open.spotify.com/artist/6sFIWsNpZYqfjUpaCgueju"
+        },
+        "href": "https://api.spotify.com/v1/artists/6sFIWsNpZYqfjUpaCgueju",
+        "id": "6sFIWsNpZYqfjUpaCgueju",
+        "name": "Carly Rae Jepsen",
+        "type": "artist",
+        "uri": "spotify:artist:6sFIWsNpZYqfjUpaCgueju"
+      }
+    ],
+    "available_markets": [
+      "ZA"
+    ],
+    "external_urls": {
+      "spotify": "https://open.spotify.com/album/0tGPJ0bkWOUmH7MEOR77qc"
+    },
+    "href": "https://api.spotify.com/v1/albums/0tGPJ0bkWOUmH7MEOR77qc",
+    "id": "0tGPJ0bkWOUmH7MEOR77qc",
+    "images": [
+      {
+        "height": 64,
+        "url": "https://i.scdn.co/image/5a73a056d0af707b4119a883d87285feda543fbb",
+        "width": 64
+      }
+    ],
+    "name": "Cut To The Feeling",
+    "release_date": "2017-05-26",
+    "release_date_precision": "day",
+    "type": "album",
+    "uri": "spotify:album:0tGPJ0bkWOUmH7MEOR77qc"
+  },
+  "artists": [
+    {
+      "external_urls": {
+        "spotify": "https://open.spotify.com/artist/6sFIWsNpZYqfjUpaCgueju"
+      },
+      "href": "https://api.spotify.com/v1/artists/6sFIWsNpZYqfjUpaCgueju",
+      "id": "6sFIWsNpZYqfjUpaCgueju",
+      "name": "Carly Rae Jepsen",
+      "type": "artist",
+      "uri": "spotify:artist:6sFIWsNpZYqfjUpaCgueju"
+    }
+  ],
+  "available_markets": [
+    "ZA"
+  ],
+  "disc_number": 1,
+  "duration_ms": 207959,
+  "explicit": false,
+  "external_ids": {
+    "isrc": "USUM71703861"
+  },
+  "external_urls": {
+    "spotify": "https://open.spotify.com/track/11dFghVXANMlKmJXsNCbNl"
+  },
+  "href": "https://api.spotify.com/v1/tracks/11dFghVXANMlKmJXsNCbNl",
+  "id": "11dFghVXANMlKmJXsNCbNl",
+  "is_local": false,
+  "name": "Cut To The Feeling",
+  "popularity": 63,
+  "preview_url": "https://p.scdn.co/mp3-preview/3eb16018c2a700240e9dfb8817b6f2d041f15eb1?cid=774b29d4f13844c495f206cafdad9c86",
+  "track_number": 1,
+  "type": "track",
+  "uri": "spotify:track:11dFghVXANMlKmJXsNCbNl"
+}
+    "#;
+    let full_track: FullTrack = deserialize(json);
+    let simplified = SimplifiedTrack::from(full_track.clone());
+    assert_eq!(simplified.id, full_track.id);
+    assert_eq!(simplified.duration, full_track.duration);
+    assert_eq!(
+        simplified.available_markets,
+        Some(full_track.available_markets.clone())
+    );
+
+    // The markets are missing when the track is requested for a market
+    let mut full_track = full_track;
+    full_track.available_markets.clear();
+    let simplified = SimplifiedTrack::from(full_track);
+    assert_eq!(simplified.available_markets, None);
+}
+
+#[test]
+fn test_full_album_into_simplified() {
+    let json_str = r#"
+{
+  "album_type": "compilation",
+  "artists": [
+    {
+      "external_urls": {
+        "spotify": "https://open.spotify.com/artist/0LyfQWJT6nXafLPZqxe9Of"
+      },
+      "href": "https://api.spotify.com/v1/artists/0LyfQWJT6nXafLPZqxe9Of",
+      "id": "0LyfQWJT6nXafLPZqxe9Of",
+      "name": "Various Artists",
+      "type": "artist",
+      "uri": "spotify:artist:0LyfQWJT6nXafLPZqxe9Of"
+    }
+  ],
+  "available_markets": ["AD", "AR", "US"],
+  "copyrights": [
+    {
+      "text": "(P) 2000 Sony Music Entertainment Inc.",
+      "type": "P"
+    }
+  ],
+  "external_ids": {
+    "upc": "5099749994324"
+  },
+  "external_urls": {
+    "spotify": "https://open.spotify.com/album/4aawyAB9vmqN3uQ7FjRGTy"
+  },
+  "genres": [],
+  "href": "https://api.spotify.com/v1/albums/4aawyAB9vmqN3uQ7FjRGTy",
+  "id": "4aawyAB9vmqN3uQ7FjRGTy",
+  "images": [
+    {
+      "height": 640,
+      "url": "https://i.scdn.co/image/ab67616d0000b2732c5b24ecfa39523a75c993c4",
+      "width": 640
+    }
+  ],
+  "label": "Mr.305/Polo Grounds Music/J Records",
+  "name": "Global Warming",
+  "popularity": 57,
+  "release_date": "2012-11-16",
+  "release_date_precision": "day",
+  "tracks": {
+    "href": "https://api.spotify.com/v1/albums/4aawyAB9vmqN3uQ7FjRGTy/tracks?offset=0&limit=50",
+    "items": [],
+    "limit": 50,
+    "next": null,
+    "offset": 0,
+    "previous": null,
+    "total": 0
+  },
+  "type": "album",
+  "uri": "spotify:album:4aawyAB9vmqN3uQ7FjRGTy"
+}
+"#;
+    let full_album: FullAlbum = deserialize(json_str);
+    let simplified = SimplifiedAlbum::from(full_album.clone());
+    assert_eq!(simplified.id.as_ref(), Some(&full_album.id));
+    assert_eq!(simplified.album_type.as_deref(), Some("compilation"));
+    assert_eq!(simplified.album_group, None);
+    assert_eq!(simplified.available_markets, ["AD", "AR", "US"]);
+    assert_eq!(simplified.release_date.as_deref(), Some("2012-11-16"));
+    assert_eq!(simplified.release_date_precision, Some(DatePrecision::Day));
+}
+
+#[test]
+fn test_full_show_into_simplified() {
+    let json_str = r#"
+{
+  "available_markets": ["AD", "AR", "US"],
+  "copyrights": [],
+  "description": "Candid conversations with entrepreneurs.",
+  "episodes": {
+    "href": "https://api.spotify.com/v1/shows/38bS44xjbVVZ3No3ByF1dJ/episodes?offset=0&limit=50",
+    "items": [],
+    "limit": 50,
+    "next": null,
+    "offset": 0,
+    "previous": null,
+    "total": 0
+  },
+  "explicit": false,
+  "external_urls": {
+    "spotify": "https://open.spotify.com/show/38bS44xjbVVZ3No3ByF1dJ"
+  },
+  "href": "https://api.spotify.com/v1/shows/38bS44xjbVVZ3No3ByF1dJ",
+  "id": "38bS44xjbVVZ3No3ByF1dJ",
+  "images": [],
+  "is_externally_hosted": false,
+  "languages": ["en"],
+  "media_type": "audio",
+  "name": "Vergiss Mein Nicht",
+  "publisher": "Vergiss Mein Nicht",
+  "type": "show",
+  "uri": "spotify:show:38bS44xjbVVZ3No3ByF1dJ"
+}
+"#;
+    let full_show: FullShow = deserialize(json_str);
+    let simplified = SimplifiedShow::from(full_show.clone());
+    assert_eq!(simplified.id, full_show.id);
+    assert_eq!(simplified.name, full_show.name);
+    assert_eq!(simplified.available_markets, full_show.available_markets);
+    assert_eq!(simplified.languages, ["en"]);
+}
+
+#[test]
+fn test_full_track_same_as() {
     let json = r#"
     {
   "album": {
@@ -597,8 +1716,28 @@ fn test_full_track() {
 }
     "#;
     let full_track: FullTrack = deserialize(json);
-    let duration = Duration::from_millis(207959);
-    assert_eq!(full_track.duration, duration);
+    // The same track requested for another market
+    let mut other = full_track.clone();
+    other.available_markets.clear();
+    assert_ne!(full_track, other);
+    assert!(full_track.same_as(&other));
+
+    // Relinked to another ID
+    other.id = Some(TrackId::from_id("6rqhFgbbKwnb9MLmUQDhG6").unwrap());
+    other.linked_from = Some(TrackLink {
+        external_urls: HashMap::new(),
+        href: "https://api.spotify.com/v1/tracks/11dFghVXANMlKmJXsNCbNl".to_owned(),
+        id: full_track.id.clone().unwrap(),
+    });
+    assert!(full_track.same_as(&other));
+
+    // Falls back to the ISRC
+    other.linked_from = None;
+    assert!(full_track.same_as(&other));
+    other
+        .external_ids
+        .insert("isrc".to_owned(), "GBAYE0601498".to_owned());
+    assert!(!full_track.same_as(&other));
 }
 
 #[test]
@@ -627,6 +1766,76 @@ fn test_resume_point_negative() {
     assert_eq!(resume_point.resume_position, duration);
 }
 
+#[test]
+fn test_resume_point_string() {
+    let json = r#"
+    {
+        "fully_played": false,
+        "resume_position_ms": "423432"
+    }
+    "#;
+    let resume_point: ResumePoint = deserialize(json);
+    let duration = Duration::from_millis(423432);
+    assert_eq!(resume_point.resume_position, duration);
+
+    let json = r#"{"fully_played": false, "resume_position_ms": "soon"}"#;
+    assert!(serde_json::from_str::<ResumePoint>(json).is_err());
+}
+
+#[test]
+fn test_currently_playing_context_strings() {
+    let json = r#"
+    {
+        "timestamp": "1607769168429",
+        "context": null,
+        "progress_ms": "22270",
+        "is_playing": false,
+        "item": null,
+        "currently_playing_type": "unknown",
+        "actions": {"disallows": {}}
+    }
+    "#;
+    let currently_playing_context: CurrentlyPlayingContext = deserialize(json);
+    assert_eq!(
+        currently_playing_context.timestamp.timestamp_millis(),
+        1607769168429
+    );
+    assert_eq!(
+        currently_playing_context.progress,
+        Some(Duration::from_millis(22270))
+    );
+}
+
+#[test]
+fn test_context_ids() {
+    let json = r#"
+    {
+        "external_urls": {
+            "spotify": "https://open.spotify.com/playlist/37i9dQZF1DXcBWIGoYBM5M"
+        },
+        "href": "https://api.spotify.com/v1/playlists/37i9dQZF1DXcBWIGoYBM5M",
+        "type": "playlist",
+        "uri": "spotify:playlist:37i9dQZF1DXcBWIGoYBM5M"
+    }
+    "#;
+    let context: Context = deserialize(json);
+    let playlist_id = PlaylistId::from_id("37i9dQZF1DXcBWIGoYBM5M").unwrap();
+    assert_eq!(context.as_playlist_id(), Some(playlist_id));
+    assert!(matches!(
+        context.play_context_id(),
+        Some(PlayContextId::Playlist(_))
+    ));
+    assert_eq!(context.as_album_id(), None);
+    assert_eq!(context.as_artist_id(), None);
+    assert_eq!(context.as_show_id(), None);
+
+    // The type and the URI don't match
+    let mut context = context;
+    context._type = Type::Album;
+    assert_eq!(context.as_album_id(), None);
+    assert!(context.play_context_id().is_none());
+}
+
 #[test]
 fn test_currently_playing_context() {
     let json = r#"
@@ -854,6 +2063,121 @@ fn test_current_playback_context() {
     assert!(current_playback_context.progress.is_none());
 }
 
+#[test]
+fn test_current_playback_context_playback_state() {
+    let json = r#"
+{
+  "device": {
+    "id": "28d0f845293d03a2713392905c6d30b6442719b5",
+    "is_active": true,
+    "is_private_session": false,
+    "is_restricted": false,
+    "name": "Web Player (Firefox)",
+    "type": "Computer",
+    "volume_percent": 100
+  },
+  "shuffle_state": false,
+  "repeat_state": "off",
+  "timestamp": 1607774342714,
+  "context": {
+    "external_urls": {
+      "spotify": "https://open.spotify.com/album/2lgOc40hhHqjUGAKMWqGxO"
+    },
+    "href": "https://api.spotify.com/v1/albums/2lgOc40hhHqjUGAKMWqGxO",
+    "type": "album",
+    "uri": "spotify:album:2lgOc40hhHqjUGAKMWqGxO"
+  },
+  "item": {
+    "album": {
+      "album_type": "single",
+      "artists": [
+        {
+          "external_urls": {
+            "spotify": "https://open.spotify.com/artist/0cGUm45nv7Z6M6qdXYQGTX"
+          },
+          "href": "https://api.spotify.com/v1/artists/0cGUm45nv7Z6M6qdXYQGTX",
+          "id": "0cGUm45nv7Z6M6qdXYQGTX",
+          "name": "Kehlani",
+          "type": "artist",
+          "uri": "spotify:artist:0cGUm45nv7Z6M6qdXYQGTX"
+        }
+      ],
+      "available_markets": [],
+      "external_urls": {
+        "spotify": "https://open.spotify.com/album/2lgOc40hhHqjUGAKMWqGxO"
+      },
+      "href": "https://api.spotify.com/v1/albums/2lgOc40hhHqjUGAKMWqGxO",
+      "id": "2lgOc40hhHqjUGAKMWqGxO",
+      "images": [
+        {
+          "height": 64,
+          "url": "https://i.scdn.co/image/ab67616d00004851fa7b2b60e85950ee93dcdc04",
+          "width": 64
+        }
+      ],
+      "name": "Playinwitme (feat. Kehlani)",
+      "release_date": "2018-03-20",
+      "release_date_precision": "day",
+      "total_tracks": 1,
+      "type": "album",
+      "uri": "spotify:album:2lgOc40hhHqjUGAKMWqGxO"
+    },
+    "artists": [
+      {
+        "external_urls": {
+          "spotify": "https://open.spotify.com/artist/0cGUm45nv7Z6M6qdXYQGTX"
+        },
+        "href": "https://api.spotify.com/v1/artists/0cGUm45nv7Z6M6qdXYQGTX",
+        "id": "0cGUm45nv7Z6M6qdXYQGTX",
+        "name": "Kehlani",
+        "type": "artist",
+        "uri": "spotify:artist:0cGUm45nv7Z6M6qdXYQGTX"
+      }
+    ],
+    "available_markets": [],
+    "disc_number": 1,
+    "duration_ms": 193093,
+    "explicit": false,
+    "external_ids": {
+      "isrc": "USAT21801141"
+    },
+    "external_urls": {
+      "spotify": "https://open.spotify.com/track/43cFjTTCD9Cni4aSL0sORz"
+    },
+    "href": "https://api.spotify.com/v1/tracks/43cFjTTCD9Cni4aSL0sORz",
+    "id": "43cFjTTCD9Cni4aSL0sORz",
+    "is_local": false,
+    "name": "Playinwitme (feat. Kehlani)",
+    "popularity": 0,
+    "preview_url": null,
+    "track_number": 1,
+    "type": "track",
+    "uri": "spotify:track:43cFjTTCD9Cni4aSL0sORz"
+  },
+  "currently_playing_type": "track",
+  "actions": {
+    "disallows": {
+      "resuming": true,
+      "skipping_prev": true
+    }
+  },
+  "is_playing": true
+}
+    "#;
+    let current_playback_context: CurrentPlaybackContext = deserialize(json);
+    let state = current_playback_context.playback_state().unwrap();
+    assert_eq!(
+        state,
+        PlaybackState {
+            item_uri: "spotify:track:43cFjTTCD9Cni4aSL0sORz".to_owned(),
+            position: Duration::ZERO,
+            device_id: Some("28d0f845293d03a2713392905c6d30b6442719b5".to_owned()),
+        }
+    );
+    let serialized = serde_json::to_string(&state).unwrap();
+    assert_eq!(deserialize::<PlaybackState>(serialized), state);
+}
+
 #[test]
 fn test_audio_analysis_track() {
     let json = r#"
@@ -937,3 +2261,406 @@ fn test_simplified_playlist() {
     );
     assert_eq!(simplified_playlist.tracks.total, 62);
 }
+
+#[test]
+fn test_start_playback_body() {
+    let body = StartPlaybackBody {
+        context_uri: Some("spotify:album:0sNOF9WDwhWunNAHPD3Baj".to_owned()),
+        offset: Some(Offset::Position(5)),
+        position_ms: Some(Duration::from_secs(3)),
+        ..Default::default()
+    };
+    assert_eq!(
+        serde_json::to_value(body).unwrap(),
+        serde_json::json!({
+            "context_uri": "spotify:album:0sNOF9WDwhWunNAHPD3Baj",
+            "offset": { "position": 5 },
+            "position_ms": 3000
+        })
+    );
+
+    let body = StartPlaybackBody {
+        uris: Some(vec!["spotify:track:4iV5W9uYEdYUVa79Axb7Rh".to_owned()]),
+        offset: Some(Offset::Uri(
+            "spotify:track:4iV5W9uYEdYUVa79Axb7Rh".to_owned(),
+        )),
+        ..Default::default()
+    };
+    assert_eq!(
+        serde_json::to_value(body).unwrap(),
+        serde_json::json!({
+            "uris": ["spotify:track:4iV5W9uYEdYUVa79Axb7Rh"],
+            "offset": { "uri": "spotify:track:4iV5W9uYEdYUVa79Axb7Rh" }
+        })
+    );
+}
+
+#[test]
+fn test_start_playback_body_validate() {
+    let uris = vec![
+        "spotify:track:4iV5W9uYEdYUVa79Axb7Rh".to_owned(),
+        "spotify:track:2DzSjFQKetFhkFCuDWhioi".to_owned(),
+    ];
+    let mut body = StartPlaybackBody {
+        uris: Some(uris),
+        offset: Some(Offset::Uri(
+            "spotify:track:2DzSjFQKetFhkFCuDWhioi".to_owned(),
+        )),
+        ..Default::default()
+    };
+    assert!(body.validate().is_ok());
+
+    body.offset = Some(Offset::Uri(
+        "spotify:track:0000000000000000000000".to_owned(),
+    ));
+    assert!(matches!(body.validate(), Err(ModelError::InvalidOffset(_))));
+
+    // The items of a context aren't known, so the offset can't be checked
+    let body = StartPlaybackBody {
+        context_uri: Some("spotify:album:0sNOF9WDwhWunNAHPD3Baj".to_owned()),
+        offset: Some(Offset::Uri(
+            "spotify:track:0000000000000000000000".to_owned(),
+        )),
+        ..Default::default()
+    };
+    assert!(body.validate().is_ok());
+}
+
+#[test]
+fn test_change_details_body() {
+    let body = ChangeDetailsBody {
+        name: Some("new name"),
+        public: Some(false),
+        ..Default::default()
+    };
+    assert_eq!(
+        serde_json::to_value(body).unwrap(),
+        serde_json::json!({ "name": "new name", "public": false })
+    );
+}
+
+#[test]
+fn test_page_extend() {
+    let mut page = Page {
+        href: "https://api.spotify.com/v1/me/tracks?offset=0&limit=2".to_owned(),
+        items: vec![1, 2],
+        limit: 2,
+        next: Some("https://api.spotify.com/v1/me/tracks?offset=2&limit=2".to_owned()),
+        offset: 0,
+        previous: None,
+        total: 3,
+    };
+    let next = Page {
+        href: "https://api.spotify.com/v1/me/tracks?offset=2&limit=2".to_owned(),
+        items: vec![3],
+        limit: 2,
+        next: None,
+        offset: 2,
+        previous: Some("https://api.spotify.com/v1/me/tracks?offset=0&limit=2".to_owned()),
+        total: 3,
+    };
+
+    page.extend(next).unwrap();
+    assert_eq!(page.items, vec![1, 2, 3]);
+    assert_eq!(page.limit, 2);
+    assert_eq!(page.offset, 0);
+    assert_eq!(page.next, None);
+    assert_eq!(page.previous, None);
+    assert_eq!(page.total, 3);
+}
+
+#[test]
+fn test_page_extend_not_contiguous() {
+    let mut page = Page {
+        href: "https://api.spotify.com/v1/me/tracks?offset=0&limit=2".to_owned(),
+        items: vec![1, 2],
+        limit: 2,
+        next: Some("https://api.spotify.com/v1/me/tracks?offset=2&limit=2".to_owned()),
+        offset: 0,
+        previous: None,
+        total: 5,
+    };
+    let skipped = Page {
+        href: "https://api.spotify.com/v1/me/tracks?offset=4&limit=2".to_owned(),
+        items: vec![5],
+        limit: 2,
+        next: None,
+        offset: 4,
+        previous: Some("https://api.spotify.com/v1/me/tracks?offset=2&limit=2".to_owned()),
+        total: 5,
+    };
+
+    let err = page.extend(skipped).unwrap_err();
+    assert!(matches!(err, ModelError::InvalidOffset(_)));
+    assert_eq!(page.items, vec![1, 2]);
+    assert_eq!(page.total, 5);
+}
+
+#[test]
+fn test_saved_albums() {
+    let json = r#"
+{
+    "href": "https://api.spotify.com/v1/me/albums?offset=0&limit=1",
+    "items": [
+        {
+            "added_at": "2021-10-29T14:06:45Z",
+            "album": {
+                "album_type": "album",
+                "artists": [
+                    {
+                        "external_urls": {
+                            "spotify": "https://open.spotify.com/artist/0LcJLqbBmaGUft1e9Mm8HV"
+                        },
+                        "href": "https://api.spotify.com/v1/artists/0LcJLqbBmaGUft1e9Mm8HV",
+                        "id": "0LcJLqbBmaGUft1e9Mm8HV",
+                        "name": "ABBA",
+                        "type": "artist",
+                        "uri": "spotify:artist:0LcJLqbBmaGUft1e9Mm8HV"
+                    }
+                ],
+                "copyrights": [
+                    {
+                        "text": "© 1976 Polar Music International AB",
+                        "type": "C"
+                    }
+                ],
+                "external_ids": {
+                    "upc": "00602547292368"
+                },
+                "external_urls": {
+                    "spotify": "https://open.spotify.com/album/1M4anG49aEs4YimBdj96Oy"
+                },
+                "genres": [],
+                "href": "https://api.spotify.com/v1/albums/1M4anG49aEs4YimBdj96Oy",
+                "id": "1M4anG49aEs4YimBdj96Oy",
+                "images": [],
+                "label": "Universal Music Group",
+                "name": "Arrival",
+                "popularity": 73,
+                "release_date": "1976-10-11",
+                "release_date_precision": "day",
+                "tracks": {
+                    "href": "https://api.spotify.com/v1/albums/1M4anG49aEs4YimBdj96Oy/tracks?offset=0&limit=50",
+                    "items": [],
+                    "limit": 50,
+                    "next": null,
+                    "offset": 0,
+                    "previous": null,
+                    "total": 0
+                },
+                "type": "album",
+                "uri": "spotify:album:1M4anG49aEs4YimBdj96Oy"
+            }
+        }
+    ],
+    "limit": 1,
+    "next": null,
+    "offset": 0,
+    "previous": null,
+    "total": 1
+}
+"#;
+    let page: Page<SavedAlbum> = deserialize(json);
+    let saved = &page.items[0];
+    let added_at = "2021-10-29T14:06:45Z".parse::<DateTime<Utc>>().unwrap();
+    assert_eq!(saved.added_at, added_at);
+    assert_eq!(saved.album.name, "Arrival");
+    assert_eq!(saved.album.album_type, AlbumType::Album);
+}
+
+#[test]
+fn test_play_history() {
+    let json_str = r#"
+    {
+        "track": {
+            "album": {
+                "album_type": "single",
+                "artists": [],
+                "external_urls": {},
+                "href": null,
+                "id": "2lgOc40hhHqjUGAKMWqGxO",
+                "images": [],
+                "name": "Playinwitme (feat. Kehlani)"
+            },
+            "artists": [],
+            "disc_number": 1,
+            "duration_ms": 191680,
+            "explicit": false,
+            "external_ids": {},
+            "external_urls": {},
+            "href": "https://api.spotify.com/v1/tracks/4F1yvJfQ7gJkrcgFJQDjOr",
+            "id": "4F1yvJfQ7gJkrcgFJQDjOr",
+            "is_local": false,
+            "name": "Playinwitme (feat. Kehlani)",
+            "popularity": 70,
+            "preview_url": null,
+            "track_number": 9,
+            "type": "track",
+            "uri": "spotify:track:4F1yvJfQ7gJkrcgFJQDjOr"
+        },
+        "played_at": "2021-03-21T09:02:08.529Z",
+        "context": {
+            "external_urls": {
+                "spotify": "https://open.spotify.com/album/2lgOc40hhHqjUGAKMWqGxO"
+            },
+            "href": "https://api.spotify.com/v1/albums/2lgOc40hhHqjUGAKMWqGxO",
+            "type": "album",
+            "uri": "spotify:album:2lgOc40hhHqjUGAKMWqGxO"
+        }
+    }
+    "#;
+    let mut history: PlayHistory = deserialize(json_str);
+    assert_eq!(history.context.as_ref().unwrap()._type, Type::Album);
+    assert_eq!(
+        history.context_uri(),
+        Some("spotify:album:2lgOc40hhHqjUGAKMWqGxO")
+    );
+
+    // Tracks played on their own have no context
+    let mut value: serde_json::Value = serde_json::from_str(json_str).unwrap();
+    value["context"] = serde_json::Value::Null;
+    history = serde_json::from_value(value).unwrap();
+    assert_eq!(history.context, None);
+    assert_eq!(history.context_uri(), None);
+}
+
+#[test]
+fn test_playlist_item_local() {
+    let json_str = r#"
+    {
+        "added_at": "2021-06-03T19:39:57Z",
+        "added_by": null,
+        "is_local": true,
+        "track": {
+            "album": {
+                "album_type": null,
+                "artists": [],
+                "external_urls": {},
+                "href": null,
+                "id": null,
+                "images": [],
+                "name": "",
+                "type": "album",
+                "uri": null
+            },
+            "artists": [],
+            "disc_number": 0,
+            "duration_ms": 216000,
+            "explicit": false,
+            "external_ids": {},
+            "external_urls": {},
+            "href": null,
+            "id": null,
+            "is_local": true,
+            "name": "Local song",
+            "popularity": 0,
+            "preview_url": null,
+            "track_number": 0,
+            "type": "track",
+            "uri": "spotify:local:::Local+song:216"
+        }
+    }
+    "#;
+    let mut item: PlaylistItem = deserialize(json_str);
+    assert!(item.spotify_id().is_none());
+
+    let track_id = TrackId::from_id("4F1yvJfQ7gJkrcgFJQDjOr").unwrap();
+    item.is_local = false;
+    if let Some(PlayableItem::Track(track)) = &mut item.track {
+        track.id = Some(track_id.clone());
+    }
+    assert_eq!(item.spotify_id().map(|id| id.uri()), Some(track_id.uri()));
+}
+
+#[test]
+fn test_search_multiple_result() {
+    let json = r#"
+    {
+        "tracks": {
+            "href": "https://api.spotify.com/v1/search?query=abba&type=track,artist&offset=0&limit=1",
+            "items": [],
+            "limit": 1,
+            "next": null,
+            "offset": 0,
+            "previous": null,
+            "total": 0
+        },
+        "artists": {
+            "href": "https://api.spotify.com/v1/search?query=abba&type=track,artist&offset=0&limit=1",
+            "items": [],
+            "limit": 1,
+            "next": null,
+            "offset": 0,
+            "previous": null,
+            "total": 0
+        }
+    }
+    "#;
+    let result: SearchMultipleResult = deserialize(json);
+    assert!(result.tracks.is_some());
+    assert!(result.artists.is_some());
+    assert!(result.albums.is_none());
+    assert!(result.playlists.is_none());
+    assert!(result.shows.is_none());
+    assert!(result.episodes.is_none());
+}
+
+#[test]
+fn test_lenient_page() {
+    let json = r#"
+    {
+        "href": "https://api.spotify.com/v1/me/tracks?offset=0&limit=3",
+        "items": [
+            {"added_at": "2021-01-01T00:00:00Z", "track": null},
+            {
+                "external_urls": {},
+                "href": "https://api.spotify.com/v1/artists/0OdUWJ0sBjDrqHygGUXeCF",
+                "id": "0OdUWJ0sBjDrqHygGUXeCF",
+                "name": "Band of Horses",
+                "type": "artist",
+                "uri": "spotify:artist:0OdUWJ0sBjDrqHygGUXeCF"
+            },
+            {
+                "external_urls": {},
+                "href": "https://api.spotify.com/v1/artists/6sFIWsNpZYqfjUpaCgueju",
+                "id": "6sFIWsNpZYqfjUpaCgueju",
+                "name": "Carly Rae Jepsen",
+                "type": "artist",
+                "uri": "spotify:artist:6sFIWsNpZYqfjUpaCgueju"
+            }
+        ],
+        "limit": 3,
+        "next": null,
+        "offset": 0,
+        "previous": null,
+        "total": 3
+    }
+    "#;
+
+    // A single malformed item fails the strict page
+    assert!(serde_json::from_str::<Page<SimplifiedArtist>>(json).is_err());
+
+    let lenient: LenientPage<SimplifiedArtist> = serde_json::from_str(json).unwrap();
+    assert_eq!(lenient.dropped, 1);
+    assert_eq!(lenient.page.total, 3);
+    let names = lenient
+        .page
+        .items
+        .iter()
+        .map(|artist| artist.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["Band of Horses", "Carly Rae Jepsen"]);
+}
+
+#[test]
+fn test_validate_genre_seeds() {
+    assert!(validate_genre_seeds(["hip-hop", "indie"], None).is_ok());
+    assert!(matches!(
+        validate_genre_seeds(["hip-hop", "hiphop"], None),
+        Err(ModelError::InvalidGenreSeed(genre)) if genre == "hiphop"
+    ));
+
+    // A fetched list takes precedence over the snapshot
+    let available = vec!["hiphop".to_owned()];
+    assert!(validate_genre_seeds(["hiphop"], Some(&available)).is_ok());
+}
@@ -1,7 +1,8 @@
 use chrono::prelude::*;
 use chrono::Duration;
 use rspotify::{
-    prelude::*, scopes, AuthCodeSpotify, ClientCredsSpotify, Config, Credentials, OAuth, Token,
+    prelude::*, scopes, AuthCodeSpotify, ClientCredsSpotify, ClientError, Config, Credentials,
+    OAuth, Token,
 };
 use std::{collections::HashMap, fs, io::Read, path::PathBuf};
 use url::Url;
@@ -139,33 +140,63 @@ fn test_parse_response_code() {
     let spotify = AuthCodeSpotify::default();
 
     // No `code` parameter
-    let url = "http://localhost:8888/callback";
-    let code = spotify.parse_response_code(url);
-    assert_eq!(code, None);
+    let url = format!(
+        "http://localhost:8888/callback?state={}",
+        spotify.oauth.state
+    );
+    let result = spotify.parse_response_code(&url);
+    assert!(matches!(result, Err(ClientError::InvalidRequest(_))));
 
     // No `state` parameter
     let url = "http://localhost:8888/callback?code=AQD0yXvFEOvw";
-    let code = spotify.parse_response_code(url);
-    assert_eq!(code, None);
+    let result = spotify.parse_response_code(url);
+    assert!(matches!(result, Err(ClientError::StateMismatch)));
 
-    // The `state` is not the expected one
-    let url = "http://localhost:8888/callback?code=AQD0yXvFEOvw?state=abc";
-    let code = spotify.parse_response_code(url);
-    assert_eq!(code, None);
+    // The `state` is not the expected one, e.g., a response to an
+    // authorization request made by someone else
+    let url = "http://localhost:8888/callback?code=AQD0yXvFEOvw&state=abc";
+    let result = spotify.parse_response_code(url);
+    assert!(matches!(result, Err(ClientError::StateMismatch)));
 
     // Both parameters, and the state is the same, so it should work
     let url = format!(
         "http://localhost:8888/callback?code=AQD0yXvFEOvw&state={}",
         spotify.oauth.state
     );
-    let code = spotify.parse_response_code(&url);
-    assert_eq!(code, Some("AQD0yXvFEOvw".to_string()));
+    let code = spotify.parse_response_code(&url).unwrap();
+    assert_eq!(code, "AQD0yXvFEOvw");
 
     // Works both ways
     let url = format!(
         "http://localhost:8888/callback?state={}&code=AQD0yXvFEOvw",
         spotify.oauth.state
     );
-    let code = spotify.parse_response_code(&url);
-    assert_eq!(code, Some("AQD0yXvFEOvw".to_string()));
+    let code = spotify.parse_response_code(&url).unwrap();
+    assert_eq!(code, "AQD0yXvFEOvw");
+
+    // The user denied access
+    let url = format!(
+        "http://localhost:8888/callback?error=access_denied&state={}",
+        spotify.oauth.state
+    );
+    let result = spotify.parse_response_code(&url);
+    assert!(
+        matches!(result, Err(ClientError::AuthorizationDenied(error)) if error == "access_denied")
+    );
+
+    // A forged error must not skip the state check
+    let url = "http://localhost:8888/callback?error=access_denied&state=abc";
+    let result = spotify.parse_response_code(url);
+    assert!(matches!(result, Err(ClientError::StateMismatch)));
+
+    let result = spotify.parse_response_code("not a url");
+    assert!(matches!(result, Err(ClientError::ParseUrl(_))));
+
+    // The values are URL-decoded
+    let url = format!(
+        "http://localhost:8888/callback?code=AQD0%2FyXv%3D%3D&state={}",
+        spotify.oauth.state
+    );
+    let code = spotify.parse_response_code(&url).unwrap();
+    assert_eq!(code, "AQD0/yXv==");
 }
@@ -1,10 +1,15 @@
 use rspotify::{
-    model::{AlbumId, AlbumType, ArtistId, Country, Market, PlaylistId, TrackId, UserId},
+    http::Method,
+    model::{
+        validate_genre_seeds, AlbumId, AlbumType, ArtistId, Country, IdError, Market, PlaylistId,
+        TrackId, UserId,
+    },
     prelude::*,
-    ClientCredsSpotify, Credentials,
+    AuthCodeSpotify, ClientCredsSpotify, ClientError, ClientResult, Credentials, Token,
 };
 
 use maybe_async::maybe_async;
+use std::{sync::Arc, time::Duration};
 
 /// Generating a new basic client for the requests.
 #[maybe_async]
@@ -26,7 +31,7 @@ pub async fn creds_client() -> ClientCredsSpotify {
 #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
 async fn test_album() {
     let birdy_uri = AlbumId::from_uri("spotify:album:0sNOF9WDwhWunNAHPD3Baj").unwrap();
-    creds_client().await.album(birdy_uri).await.unwrap();
+    creds_client().await.album(birdy_uri, None).await.unwrap();
 }
 
 #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
@@ -126,6 +131,22 @@ async fn test_audios_features() {
         .unwrap();
 }
 
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_audios_features_bulk() {
+    let tracks_ids = [
+        TrackId::from_uri("spotify:track:4JpKVNYnVcJ8tuMKjAj50A").unwrap(),
+        TrackId::from_uri("spotify:track:24JygzOLM0EmRQeGtFcIcG").unwrap(),
+        TrackId::from_uri("spotify:track:6rqhFgbbKwnb9MLmUQDhG6").unwrap(),
+    ];
+    let result = creds_client()
+        .await
+        .tracks_features_bulk(tracks_ids, Some(2))
+        .await
+        .unwrap();
+    assert!(result.is_complete());
+    assert_eq!(result.ok.len(), 3);
+}
+
 #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
 async fn test_user() {
     let birdy_uri = UserId::from_id("tuggareutangranser").unwrap();
@@ -138,6 +159,28 @@ async fn test_track() {
     creds_client().await.track(birdy_uri).await.unwrap();
 }
 
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_track_value() {
+    let birdy_uri = TrackId::from_uri("spotify:track:6rqhFgbbKwnb9MLmUQDhG6").unwrap();
+    let track = creds_client().await.track_value(birdy_uri).await.unwrap();
+    assert_eq!(track["id"], "6rqhFgbbKwnb9MLmUQDhG6");
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_request_raw() {
+    let track = creds_client()
+        .await
+        .request_raw(
+            Method::Get,
+            "tracks/6rqhFgbbKwnb9MLmUQDhG6",
+            &[("market", "US")],
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(track["id"], "6rqhFgbbKwnb9MLmUQDhG6");
+}
+
 #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
 async fn test_tracks() {
     let track_uris = [
@@ -157,6 +200,16 @@ async fn test_existing_playlist() {
         .unwrap();
 }
 
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_playlist_cover_image() {
+    let playlist_id = PlaylistId::from_id("37i9dQZF1DZ06evO45P0Eo").unwrap();
+    creds_client()
+        .await
+        .playlist_cover_image(playlist_id)
+        .await
+        .unwrap();
+}
+
 #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
 async fn test_fake_playlist() {
     let playlist_id = PlaylistId::from_id("fakeid").unwrap();
@@ -166,6 +219,7 @@ async fn test_fake_playlist() {
 
 mod test_pagination {
     use super::*;
+    use rspotify::{clients::pagination::paginate_with_progress, model::Page};
 
     static ALBUM: &str = "spotify:album:2T7DdrOvsqOqU9bGTkjBYu";
     static SONG_NAMES: &[&str; 10] = &[
@@ -215,4 +269,360 @@ mod test_pagination {
 
         assert_eq!(names, SONG_NAMES);
     }
+
+    /// This test continues a manually requested page of 2 items by following
+    /// the `next` URLs.
+    #[cfg(feature = "__sync")]
+    #[test]
+    fn test_page_stream_sync() {
+        let client = creds_client();
+        let album = AlbumId::from_uri(ALBUM).unwrap();
+
+        let first = client.album_track_manual(&album, Some(2), None).unwrap();
+        let names = client
+            .page_stream(first)
+            .map(|track| track.unwrap().name)
+            .collect::<Vec<_>>();
+
+        assert_eq!(names, SONG_NAMES);
+    }
+
+    /// This test continues a manually requested page of 2 items by following
+    /// the `next` URLs.
+    #[cfg(feature = "__async")]
+    #[tokio::test]
+    async fn test_page_stream_async() {
+        use futures_util::StreamExt;
+
+        let client = creds_client().await;
+        let album = AlbumId::from_uri(ALBUM).unwrap();
+
+        let first = client
+            .album_track_manual(&album, Some(2), None)
+            .await
+            .unwrap();
+        let names = client
+            .page_stream(first)
+            .map(|track| track.unwrap().name)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(names, SONG_NAMES);
+    }
+
+    /// A page of a list of 5 numbers, so that no request has to be sent.
+    fn numbers_page(limit: u32, offset: u32) -> ClientResult<Page<u32>> {
+        let total = 5;
+        let end = total.min(offset + limit);
+        Ok(Page {
+            href: String::new(),
+            items: (offset..end).collect(),
+            limit,
+            next: (end < total).then(String::new),
+            offset,
+            previous: None,
+            total,
+        })
+    }
+
+    /// This test reports the progress of a list of 5 items, with 3 requests.
+    #[cfg(feature = "__sync")]
+    #[test]
+    fn test_pagination_progress_sync() {
+        let mut progress = Vec::new();
+        let items = paginate_with_progress(numbers_page, 2, |fetched, total| {
+            progress.push((fetched, total))
+        })
+        .map(Result::unwrap)
+        .collect::<Vec<_>>();
+
+        assert_eq!(items, [0, 1, 2, 3, 4]);
+        assert_eq!(progress, [(2, 5), (4, 5), (5, 5)]);
+    }
+
+    /// This test reports the progress of a list of 5 items, with 3 requests.
+    #[cfg(feature = "__async")]
+    #[tokio::test]
+    async fn test_pagination_progress_async() {
+        use futures_util::StreamExt;
+
+        let mut progress = Vec::new();
+        let items = paginate_with_progress(
+            |limit, offset| async move { numbers_page(limit, offset) },
+            2,
+            |fetched, total| progress.push((fetched, total)),
+        )
+        .map(Result::unwrap)
+        .collect::<Vec<_>>()
+        .await;
+
+        assert_eq!(items, [0, 1, 2, 3, 4]);
+        assert_eq!(progress, [(2, 5), (4, 5), (5, 5)]);
+    }
+
+    static TRACKS: &[&str; 3] = &[
+        "spotify:track:4JpKVNYnVcJ8tuMKjAj50A",
+        "spotify:track:24JygzOLM0EmRQeGtFcIcG",
+        "spotify:track:6rqhFgbbKwnb9MLmUQDhG6",
+    ];
+
+    /// This test streams the audio features of 3 tracks, with 2 requests.
+    #[cfg(feature = "__sync")]
+    #[test]
+    fn test_tracks_features_stream_sync() {
+        let client = creds_client();
+        let ids = TRACKS.iter().map(|uri| TrackId::from_uri(uri).unwrap());
+
+        let indices = client
+            .tracks_features_stream(ids, Some(2))
+            .map(|item| item.unwrap().0)
+            .collect::<Vec<_>>();
+
+        assert_eq!(indices, [0, 1, 2]);
+    }
+
+    /// This test streams the audio features of 3 tracks, with 2 requests.
+    #[cfg(feature = "__async")]
+    #[tokio::test]
+    async fn test_tracks_features_stream_async() {
+        use futures_util::StreamExt;
+
+        let client = creds_client().await;
+        let ids = TRACKS.iter().map(|uri| TrackId::from_uri(uri).unwrap());
+
+        let indices = client
+            .tracks_features_stream(ids, Some(2))
+            .map(|item| item.unwrap().0)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(indices, [0, 1, 2]);
+    }
+
+    /// This test streams 3 tracks, with 2 requests.
+    #[cfg(feature = "__sync")]
+    #[test]
+    fn test_tracks_stream_sync() {
+        let client = creds_client();
+        let ids = TRACKS.iter().map(|uri| TrackId::from_uri(uri).unwrap());
+
+        let tracks = client
+            .tracks_stream(ids, None, Some(2))
+            .map(|item| item.unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(tracks.len(), 3);
+        for (i, (index, track)) in tracks.iter().enumerate() {
+            assert_eq!(*index, i);
+            assert_eq!(track.id.as_ref().unwrap().uri(), TRACKS[i]);
+        }
+    }
+
+    /// This test streams 3 tracks, with 2 requests.
+    #[cfg(feature = "__async")]
+    #[tokio::test]
+    async fn test_tracks_stream_async() {
+        use futures_util::StreamExt;
+
+        let client = creds_client().await;
+        let ids = TRACKS.iter().map(|uri| TrackId::from_uri(uri).unwrap());
+
+        let tracks = client
+            .tracks_stream(ids, None, Some(2))
+            .map(|item| item.unwrap())
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(tracks.len(), 3);
+        for (i, (index, track)) in tracks.iter().enumerate() {
+            assert_eq!(*index, i);
+            assert_eq!(track.id.as_ref().unwrap().uri(), TRACKS[i]);
+        }
+    }
+
+    /// An invalid chunk size is the only item, without sending any request.
+    #[cfg(feature = "__sync")]
+    #[test]
+    fn test_tracks_features_stream_invalid_chunk_size_sync() {
+        let client = ClientCredsSpotify::default();
+        let ids = TRACKS.iter().map(|uri| TrackId::from_uri(uri).unwrap());
+
+        let items = client
+            .tracks_features_stream(ids, Some(0))
+            .collect::<Vec<_>>();
+
+        assert_eq!(items.len(), 1);
+        assert!(matches!(
+            items[0],
+            Err(ClientError::InvalidChunkSize { size: 0, max: 100 })
+        ));
+    }
+
+    /// An invalid chunk size is the only item, without sending any request.
+    #[cfg(feature = "__async")]
+    #[tokio::test]
+    async fn test_tracks_features_stream_invalid_chunk_size_async() {
+        use futures_util::StreamExt;
+
+        let client = ClientCredsSpotify::default();
+        let ids = TRACKS.iter().map(|uri| TrackId::from_uri(uri).unwrap());
+
+        let items = client
+            .tracks_features_stream(ids, Some(0))
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(items.len(), 1);
+        assert!(matches!(
+            items[0],
+            Err(ClientError::InvalidChunkSize { size: 0, max: 100 })
+        ));
+    }
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_recommendations_from_token() {
+    // Fails before any request is sent, so no token is needed
+    let result = ClientCredsSpotify::default()
+        .recommendations(
+            [],
+            None::<Vec<ArtistId>>,
+            Some(["indie"]),
+            None::<Vec<TrackId>>,
+            Some(Market::FromToken),
+            None,
+        )
+        .await;
+    assert!(matches!(result, Err(ClientError::FromTokenWithoutUser)));
+}
+
+#[test]
+fn test_with_retry() {
+    let spotify = ClientCredsSpotify::default();
+    let retrying = spotify.with_retry(5);
+    assert_eq!(retrying.get_config().max_retries, 5);
+    assert_eq!(retrying.without_retry().get_config().max_retries, 0);
+
+    // The original client is left untouched, but the token is shared
+    assert_eq!(spotify.get_config().max_retries, 0);
+    assert!(Arc::ptr_eq(&spotify.get_token(), &retrying.get_token()));
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_available_markets() {
+    let markets = creds_client().await.available_markets().await.unwrap();
+    assert!(markets
+        .iter()
+        .any(|market| market.market == Some(Market::Country(Country::UnitedStates))));
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_available_genre_seeds() {
+    let spotify = creds_client().await;
+    let genres = spotify.available_genre_seeds().await.unwrap();
+    assert!(!genres.is_empty());
+    validate_genre_seeds(genres.iter().map(String::as_str), Some(&genres)).unwrap();
+}
+
+#[test]
+fn test_invalid_id_error() {
+    fn parse(uri: &str) -> ClientResult<TrackId<'_>> {
+        Ok(TrackId::from_id_or_uri(uri)?)
+    }
+
+    assert!(parse("spotify:track:4iV5W9uYEdYUVa79Axb7Rh").is_ok());
+    assert!(matches!(
+        parse("spotify:album:4iV5W9uYEdYUVa79Axb7Rh"),
+        Err(ClientError::InvalidId(IdError::InvalidType))
+    ));
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_set_token() {
+    let spotify = ClientCredsSpotify::default();
+    let current = spotify.token().await;
+    assert_eq!(current, None);
+
+    let token = Token {
+        access_token: "test-access-token".to_owned(),
+        ..Default::default()
+    };
+    spotify.set_token(token.clone()).await;
+    let current = spotify.token().await;
+    assert_eq!(current, Some(token));
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_download_missing_preview() {
+    let spotify = ClientCredsSpotify::default();
+    let result = spotify.download_preview(None).await;
+    assert!(matches!(result, Err(ClientError::MissingPreview)));
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_search_multiple_without_types() {
+    let spotify = ClientCredsSpotify::default();
+
+    // Fails before sending the request, so no token is needed
+    let result = spotify
+        .search_multiple("weezer", [], None, None, None, None)
+        .await;
+    assert!(matches!(result, Err(ClientError::InvalidRequest(_))));
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_device_auth_invalid_response() {
+    let spotify = AuthCodeSpotify::default();
+    let handle = spotify.begin_device_auth().unwrap();
+    assert!(handle
+        .url()
+        .starts_with("https://accounts.spotify.com/authorize"));
+
+    // The state doesn't match the one in the authorization URL
+    let result = handle
+        .complete("http://localhost:8888/callback?code=abc&state=other")
+        .await;
+    assert!(matches!(result, Err(ClientError::StateMismatch)));
+
+    let result = handle.complete("  ").await;
+    assert!(matches!(result, Err(ClientError::InvalidRequest(_))));
+
+    // Polls until a response is available, failing before requesting the token
+    let mut attempts = 0;
+    let response = || {
+        attempts += 1;
+        (attempts == 3).then(|| "not a code".to_owned())
+    };
+    let result = handle.poll(response, Duration::ZERO, 5).await;
+    assert!(matches!(result, Err(ClientError::InvalidRequest(_))));
+    assert_eq!(attempts, 3);
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_device_auth_poll_timeout() {
+    let spotify = AuthCodeSpotify::default();
+    let handle = spotify.begin_device_auth().unwrap();
+
+    let mut attempts = 0;
+    let response = || {
+        attempts += 1;
+        None
+    };
+    let result = handle.poll(response, Duration::ZERO, 3).await;
+    assert!(matches!(result, Err(ClientError::AuthTimeout)));
+    assert_eq!(attempts, 3);
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+async fn test_playlist_check_follow_too_many_ids() {
+    let spotify = ClientCredsSpotify::default();
+    let playlist_id = PlaylistId::from_id("37i9dQZF1DXcBWIGoYBM5M").unwrap();
+    let user_ids = vec![UserId::from_id("possan").unwrap(); 6];
+
+    // Fails before sending the request, so no token is needed
+    let result = spotify.playlist_check_follow(playlist_id, &user_ids).await;
+    assert!(matches!(
+        result,
+        Err(ClientError::TooManyIds { count: 6, max: 5 })
+    ));
 }
@@ -19,14 +19,14 @@ use rspotify::{
     clients::pagination::Paginator,
     model::{
         AlbumId, ArtistId, Country, CurrentPlaybackContext, Device, EpisodeId, FullPlaylist,
-        ItemPositions, Market, Offset, PlaylistId, RecommendationsAttribute, RepeatState,
-        SearchType, ShowId, TimeLimits, TimeRange, TrackId, UserId,
+        ItemPositions, Market, Offset, PlayableItem, PlaylistId, RecommendationsAttribute,
+        RepeatState, SearchResult, SearchType, ShowId, TimeLimits, TimeRange, TrackId, UserId,
     },
     prelude::*,
     scopes, AuthCodeSpotify, ClientResult, Credentials, OAuth, Token,
 };
 
-use std::env;
+use std::{collections::HashSet, env};
 
 use chrono::{prelude::*, Duration};
 use maybe_async::maybe_async;
@@ -83,7 +83,7 @@ pub async fn oauth_client() -> AuthCodeSpotify {
         };
 
         let spotify = AuthCodeSpotify::new(creds, oauth);
-        *spotify.token.lock().await.unwrap() = Some(token);
+        spotify.set_token(token).await;
         spotify.refresh_token().await.unwrap();
         spotify
     } else {
@@ -170,6 +170,32 @@ async fn test_current_user_followed_artists() {
         .unwrap();
 }
 
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+#[ignore]
+async fn test_current_user_all_followed_artists() {
+    let client = oauth_client().await;
+    let first_page = client
+        .current_user_followed_artists(None, None)
+        .await
+        .unwrap();
+    let artists = client.current_user_all_followed_artists().await.unwrap();
+    if let Some(total) = first_page.total {
+        assert_eq!(artists.len(), total as usize);
+    }
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+#[ignore]
+async fn test_current_user_all_saved_tracks() {
+    let client = oauth_client().await;
+    let first_page = client
+        .current_user_saved_tracks_manual(None, Some(1), None)
+        .await
+        .unwrap();
+    let tracks = client.current_user_all_saved_tracks(None).await.unwrap();
+    assert_eq!(tracks.len(), first_page.total as usize);
+}
+
 #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
 #[ignore]
 async fn test_current_user_playing_track() {
@@ -506,6 +532,65 @@ async fn test_search_track() {
         .unwrap();
 }
 
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+#[ignore]
+async fn test_search_n() {
+    let query = "abba";
+    let result = oauth_client()
+        .await
+        .search_n(query, SearchType::Track, None, None, 120, false)
+        .await
+        .unwrap();
+
+    match result {
+        SearchResult::Tracks(page) => assert!(page.items.len() <= 120),
+        _ => panic!("expected a page of tracks"),
+    }
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+#[ignore]
+async fn test_search_n_dedup() {
+    let query = "abba";
+    let result = oauth_client()
+        .await
+        .search_n(query, SearchType::Track, None, None, 120, true)
+        .await
+        .unwrap();
+
+    match result {
+        SearchResult::Tracks(page) => {
+            let ids = page.items.iter().filter_map(|track| track.id.as_ref());
+            let unique = ids.clone().collect::<HashSet<_>>();
+            assert_eq!(unique.len(), ids.count());
+        }
+        _ => panic!("expected a page of tracks"),
+    }
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+#[ignore]
+async fn test_search_multiple() {
+    let query = "abba";
+    let result = oauth_client()
+        .await
+        .search_multiple(
+            query,
+            [SearchType::Track, SearchType::Artist],
+            None,
+            None,
+            Some(10),
+            None,
+        )
+        .await
+        .unwrap();
+
+    assert!(result.tracks.is_some());
+    assert!(result.artists.is_some());
+    assert!(result.albums.is_none());
+    assert!(result.playlists.is_none());
+}
+
 // This also tests percentage signs in search queries to avoid regressions of
 // https://github.com/ramsayleung/rspotify/issues/141
 #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
@@ -556,6 +641,73 @@ async fn test_shuffle() {
     }
 }
 
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+#[ignore]
+async fn test_user_market() {
+    let client = oauth_client().await;
+
+    let market = client.user_market().await.unwrap();
+    // The second one is cached
+    let cached = client.user_market().await.unwrap();
+    assert_eq!(market, cached);
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+#[ignore]
+async fn test_player() {
+    let client = oauth_client().await;
+    let player = client.player(None);
+
+    // Saving the previous state to restore it later
+    let backup = client.current_playback(None, None::<&[_]>).await.unwrap();
+
+    player.shuffle(true).await.unwrap();
+    player.repeat(RepeatState::Context).await.unwrap();
+
+    if let Some(backup) = backup {
+        player.shuffle(backup.shuffle_state).await.unwrap();
+        player.repeat(backup.repeat_state).await.unwrap();
+    }
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+#[ignore]
+async fn test_player_and_fetch() {
+    let client = oauth_client().await;
+    let player = client.player(None);
+
+    if let Some(backup) = player.playback().await.unwrap() {
+        let volume = backup.device.volume_percent.unwrap_or(50) as u8;
+        let playback = player.volume_and_fetch(volume).await.unwrap();
+        assert!(playback.is_some());
+    }
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+#[ignore]
+async fn test_playback_state() {
+    let client = oauth_client().await;
+
+    // Restoring the snapshot right away should leave the playback untouched
+    if let Some(state) = client.capture_playback_state().await.unwrap() {
+        client.restore_playback_state(&state).await.unwrap();
+    }
+}
+
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+#[ignore]
+async fn test_resume_from_history() {
+    let client = oauth_client().await;
+    let history = client
+        .current_user_recently_played(Some(1), None)
+        .await
+        .unwrap();
+
+    if let Some(item) = history.items.first() {
+        client.resume_from_history(item, None).await.unwrap();
+    }
+}
+
 #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
 #[ignore]
 async fn test_user_follow_artist() {
@@ -627,12 +779,12 @@ async fn check_playlist_create(client: &AuthCodeSpotify) -> FullPlaylist {
     let name = "A New Playlist-update";
     let description = "A random description";
     client
-        .playlist_change_detail(
+        .playlist_change_details(
             playlist.id.as_ref(),
             Some(name),
             Some(true),
-            Some(description),
             Some(false),
+            Some(description),
         )
         .await
         .unwrap();
@@ -738,6 +890,22 @@ async fn check_playlist_tracks(client: &AuthCodeSpotify, playlist: &FullPlaylist
         replaced_tracks.len() as i32 - 5,
     )
     .await;
+
+    // Removes the remaining episode
+    client
+        .playlist_remove_where(
+            playlist.id.as_ref(),
+            |item| matches!(item.track, Some(PlayableItem::Episode(_))),
+            None,
+        )
+        .await
+        .unwrap();
+    check_num_tracks(
+        client,
+        playlist.id.as_ref(),
+        replaced_tracks.len() as i32 - 6,
+    )
+    .await;
 }
 
 #[maybe_async]
@@ -761,6 +929,30 @@ async fn check_playlist_follow(client: &AuthCodeSpotify, playlist: &FullPlaylist
         .unwrap();
 }
 
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+#[ignore]
+async fn test_playlist_create_with_tracks() {
+    let client = oauth_client().await;
+    let user = client.me().await.unwrap();
+    let tracks = [
+        TrackId::from_uri("spotify:track:4iV5W9uYEdYUVa79Axb7Rh").unwrap(),
+        TrackId::from_uri("spotify:track:2DzSjFQKetFhkFCuDWhioi").unwrap(),
+    ];
+
+    let playlist = client
+        .user_playlist_create_with_tracks(
+            user.id.as_ref(),
+            "A New Playlist With Tracks",
+            Some(false),
+            tracks,
+        )
+        .await
+        .unwrap();
+    assert_eq!(playlist.tracks.total, 2);
+
+    client.playlist_unfollow(playlist.id).await.unwrap();
+}
+
 #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
 #[ignore]
 async fn test_playlist() {
@@ -807,6 +999,19 @@ async fn test_add_queue() {
         .unwrap();
 }
 
+#[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
+#[ignore]
+async fn test_get_a_show() {
+    let show = ShowId::from_id("5CfCWKI5pZ28U0uOzXkDHe").unwrap();
+
+    let show = oauth_client()
+        .await
+        .get_a_show(show, Some(Market::Country(Country::UnitedStates)))
+        .await
+        .unwrap();
+    assert_eq!(show.id.id(), "5CfCWKI5pZ28U0uOzXkDHe");
+}
+
 #[maybe_async::test(feature = "__sync", async(feature = "__async", tokio::test))]
 #[ignore]
 async fn test_get_several_shows() {